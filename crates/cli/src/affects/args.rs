@@ -0,0 +1,22 @@
+use crate::args::{Files, Format, WidgetPath, parse_widget_path};
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    /// affected widget, by path names or indexes
+    ///
+    /// e.g. "main_canvas.Arrival" or "1.3"
+    #[arg(value_parser = parse_widget_path, value_name = "PATH")]
+    pub path: WidgetPath,
+
+    #[command(flatten)]
+    pub files: Files,
+
+    /// only analyze this sequence (all of them by default)
+    #[arg(short, long, value_name = "NAME")]
+    pub sequence: Option<String>,
+
+    /// optionally output as JSON, instead of table (default)
+    #[arg(value_enum, long, default_value_t = Format::Table)]
+    pub output: Format,
+}
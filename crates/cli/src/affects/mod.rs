@@ -0,0 +1,117 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::{
+    anim::InkAnimAnimationLibraryResource,
+    widget::{WidgetTree, inkWidgetLibraryResource},
+};
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+use crate::args::Format;
+
+pub(crate) fn affects(
+    args: Args,
+    widget: inkWidgetLibraryResource,
+    anim: InkAnimAnimationLibraryResource,
+) {
+    let path = args
+        .path
+        .resolve(&widget)
+        .unwrap_or_else(|| panic!("couldn't find widget {}", args.path));
+    if let Some(ref name) = args.sequence
+        && !anim.sequences.iter().any(|x| x.name() == name)
+    {
+        panic!("no sequence named {name}");
+    }
+    let influences = anim
+        .sequences
+        .iter()
+        .filter(|x| args.sequence.as_ref().is_none_or(|name| x.name() == name))
+        .flat_map(|sequence| {
+            sequence
+                .data
+                .influences(&path)
+                .into_iter()
+                .map(move |x| (sequence.name(), x))
+        })
+        .collect::<Vec<_>>();
+    match args.output {
+        Format::Table => {
+            if influences.is_empty() {
+                println!("no anim affects {}", args.path);
+                return;
+            }
+            let mut table = Table::new();
+            table.style = TableStyle::rounded();
+            table.add_row(Row::new(
+                [
+                    "sequence",
+                    "definition",
+                    "interpolator",
+                    "property",
+                    "window",
+                    "target",
+                    "via",
+                ]
+                .into_iter()
+                .map(|x| TableCell::builder(x).alignment(Alignment::Center).build())
+                .collect::<Vec<_>>(),
+            ));
+            for (sequence, influence) in influences.iter() {
+                let target = widget
+                    .get_path_names(&influence.target)
+                    .and_then(|x| x.last().cloned())
+                    .unwrap_or_default();
+                table.add_row(Row::new(vec![
+                    TableCell::new(sequence),
+                    TableCell::builder(influence.definition)
+                        .alignment(Alignment::Center)
+                        .build(),
+                    TableCell::builder(format!(
+                        "{} ({})",
+                        influence.interpolator, influence.handle_id
+                    ))
+                    .alignment(Alignment::Center)
+                    .build(),
+                    TableCell::new(&influence.property),
+                    TableCell::builder(format!(
+                        "{}s → {}s",
+                        influence.window.start, influence.window.end
+                    ))
+                    .alignment(Alignment::Right)
+                    .build(),
+                    TableCell::new(format!(
+                        "{} {target}",
+                        influence
+                            .target
+                            .iter()
+                            .map(|x| x.to_string())
+                            .collect::<Vec<_>>()
+                            .join(".")
+                    )),
+                    TableCell::new(if influence.inherited {
+                        "⬆️ ancestor"
+                    } else {
+                        "🎯 itself"
+                    }),
+                ]));
+            }
+            println!("{}", table.render());
+        }
+        Format::Json => {
+            let influences = influences
+                .iter()
+                .map(|(sequence, influence)| {
+                    let mut value = serde_json::to_value(influence).unwrap();
+                    value["sequence"] = serde_json::Value::from(*sequence);
+                    value
+                })
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string_pretty(&influences).unwrap());
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use inkanim_types::Fidelity;
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// pull a single sequence out into its own .inkanim
+    #[command(name = "extract")]
+    Extract(Extract),
+    /// write every sequence into its own .inkanim
+    #[command(name = "split")]
+    Split(Split),
+    /// gather sequences from several .inkanim into a single one
+    #[command(name = "merge")]
+    Merge(Merge),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct Extract {
+    /// .inkanim path
+    #[arg(short, long, value_name = "FILE")]
+    pub anim: PathBuf,
+
+    /// name of the sequence to extract
+    #[arg(short, long, value_name = "NAME")]
+    pub sequence: String,
+
+    /// .inkanim output path
+    #[arg(short, long, value_name = "FILE")]
+    pub out: PathBuf,
+
+    /// import-ready for WolvenKit (default), or trimmed for reading
+    #[arg(value_enum, long, default_value_t = Fidelity::WolvenKit)]
+    pub fidelity: Fidelity,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct Split {
+    /// .inkanim path
+    #[arg(short, long, value_name = "FILE")]
+    pub anim: PathBuf,
+
+    /// output directory, where each sequence gets written as "<name>.json"
+    #[arg(short, long, value_name = "DIR")]
+    pub out: PathBuf,
+
+    /// import-ready for WolvenKit (default), or trimmed for reading
+    #[arg(value_enum, long, default_value_t = Fidelity::WolvenKit)]
+    pub fidelity: Fidelity,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct Merge {
+    /// .inkanim paths, the first one being merged into
+    ///
+    /// e.g. "-a first.json -a second.json"
+    #[arg(short, long, value_name = "FILE", num_args = 1, required = true)]
+    pub anim: Vec<PathBuf>,
+
+    /// .inkanim output path
+    #[arg(short, long, value_name = "FILE")]
+    pub out: PathBuf,
+
+    /// import-ready for WolvenKit (default), or trimmed for reading
+    #[arg(value_enum, long, default_value_t = Fidelity::WolvenKit)]
+    pub fidelity: Fidelity,
+}
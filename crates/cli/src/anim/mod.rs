@@ -0,0 +1,78 @@
+mod args;
+pub(crate) use args::{Args, Command};
+
+use std::path::Path;
+
+use inkanim_types::{Fidelity, File, anim::InkAnimAnimationLibraryResource};
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+use crate::read::read_anim;
+
+fn write(path: &Path, fidelity: Fidelity, file: &File<InkAnimAnimationLibraryResource>) {
+    let json = fidelity
+        .scope(|| serde_json::to_string_pretty(file))
+        .expect("serialize .inkanim");
+    std::fs::write(path, json).expect("write .inkanim");
+}
+
+pub(crate) fn anim(args: Args) {
+    match args.command {
+        Command::Extract(args) => {
+            let mut file = read_anim(&args.anim);
+            file.data.root_chunk = file
+                .data
+                .root_chunk
+                .extract(&args.sequence)
+                .unwrap_or_else(|| panic!("no sequence named {}", args.sequence));
+            write(&args.out, args.fidelity, &file);
+            println!("extracted anim written to {}", args.out.display());
+        }
+        Command::Split(args) => {
+            let file = read_anim(&args.anim);
+            std::fs::create_dir_all(&args.out).expect("create output directory");
+            for library in file.data.root_chunk.split() {
+                let path = args
+                    .out
+                    .join(format!("{}.json", library.sequences[0].name()));
+                let mut file = file.clone();
+                file.data.root_chunk = library;
+                write(&path, args.fidelity, &file);
+                println!("split anim written to {}", path.display());
+            }
+        }
+        Command::Merge(args) => {
+            let mut paths = args.anim.iter();
+            let mut file = read_anim(paths.next().expect("at least one .inkanim"));
+            let mut table = Table::new();
+            table.style = TableStyle::rounded();
+            table.add_row(Row::new(vec![
+                TableCell::builder("from")
+                    .alignment(Alignment::Center)
+                    .build(),
+                TableCell::builder("sequence")
+                    .alignment(Alignment::Center)
+                    .build(),
+                TableCell::builder("renamed")
+                    .alignment(Alignment::Center)
+                    .build(),
+            ]));
+            for path in paths {
+                let other = read_anim(path).resource();
+                for (previous, new) in file.data.root_chunk.merge(other) {
+                    table.add_row(Row::new(vec![
+                        TableCell::new(path.display()),
+                        TableCell::new(previous),
+                        TableCell::new(new),
+                    ]));
+                }
+            }
+            write(&args.out, args.fidelity, &file);
+            println!("{}", table.render());
+            println!("merged anim written to {}", args.out.display());
+        }
+    }
+}
@@ -3,13 +3,13 @@ use std::path::PathBuf;
 use clap::ValueEnum;
 use inkanim_types::{
     anim::{InkAnimAnimationLibraryResource, InkAnimInterpolatorType},
-    widget::inkWidgetLibraryResource,
+    widget::{WidgetTree, inkWidgetLibraryResource, occurrence},
 };
 
 pub struct DualResources {
     pub widget: inkWidgetLibraryResource,
     pub anim: InkAnimAnimationLibraryResource,
-    pub filter_by_path: Option<Vec<usize>>,
+    pub filter_by_paths: Option<Vec<Vec<usize>>>,
     pub filter_by_type: Option<InkAnimInterpolatorType>,
     pub show_path_names: bool,
 }
@@ -51,8 +51,82 @@ pub struct PathNames {
     /// filter by widget path name(s)
     ///
     /// e.g. "main_canvas.Arrival.Arrival_GPS_Canvas.Arrival_GPS_Elements_Canvas"
+    ///
+    /// note: siblings sharing a name can be told apart by occurrence, e.g. "warning_Flex1[2]"
     #[arg(short, long, value_parser = parse_path_names, value_name = "NAMES")]
     pub path: std::vec::Vec<String>,
+
+    /// replace each name which cannot be found by its single closest match
+    #[arg(long)]
+    pub fuzzy: bool,
+}
+
+impl PathNames {
+    /// path names as given, or as corrected with `--fuzzy`,
+    /// exiting with the available names and suggestions when they cannot be found
+    pub fn resolve(&self, widget: &inkWidgetLibraryResource) -> Vec<String> {
+        let path = &self.path;
+        if path.is_empty() {
+            panic!("please specify widget path names");
+        }
+        let names = path.iter().map(|x| x.as_str()).collect::<Vec<_>>();
+        let unresolved = if self.fuzzy {
+            match widget.fuzzy_path_names(&names) {
+                Ok(corrected) => {
+                    if corrected != *path {
+                        eprintln!("using {}", corrected.join("."));
+                    }
+                    return corrected;
+                }
+                Err(unresolved) => unresolved,
+            }
+        } else {
+            match widget.unresolved(&names) {
+                Some(unresolved) => unresolved,
+                None => return path.clone(),
+            }
+        };
+        let prefix = widget
+            .unambiguous_path_names(&unresolved.resolved)
+            .unwrap_or_default();
+        eprintln!(
+            "couldn't find {} under {}",
+            unresolved.missing,
+            if prefix.is_empty() {
+                "the root widget".to_string()
+            } else {
+                format!(
+                    "{} ({})",
+                    prefix.join("."),
+                    unresolved
+                        .resolved
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join(".")
+                )
+            }
+        );
+        if unresolved.namesakes > 0 {
+            eprintln!(
+                "only {} sibling(s) named {}",
+                unresolved.namesakes,
+                occurrence(&unresolved.missing).0
+            );
+        }
+        if unresolved.available.is_empty() {
+            eprintln!("which has no children");
+        } else {
+            eprintln!("available: {}", unresolved.available.join(", "));
+        }
+        if !unresolved.suggestions.is_empty() {
+            eprintln!("did you mean: {}?", unresolved.suggestions.join(", "));
+            if !self.fuzzy {
+                eprintln!("hint: --fuzzy picks the closest match when a single one stands out");
+            }
+        }
+        std::process::exit(1);
+    }
 }
 
 #[derive(clap::Args, Debug)]
@@ -76,6 +150,67 @@ pub(crate) fn parse_path_names(path: &str) -> Result<Vec<String>, std::io::Error
         .collect())
 }
 
+pub(crate) fn parse_regex(pattern: &str) -> Result<regex::Regex, regex::Error> {
+    regex::Regex::new(pattern)
+}
+
+/// widget path, given either as names or indexes
+#[derive(Debug, Clone)]
+pub enum WidgetPath {
+    Indexes(Vec<usize>),
+    Names(Vec<String>),
+}
+
+impl WidgetPath {
+    /// path indexes, if the widget exists
+    pub fn resolve(&self, widget: &inkWidgetLibraryResource) -> Option<Vec<usize>> {
+        match self {
+            Self::Indexes(indexes) => widget.get_path_names(indexes).map(|_| indexes.clone()),
+            Self::Names(names) => {
+                widget.get_path_indexes(&names.iter().map(|x| x.as_str()).collect::<Vec<_>>())
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for WidgetPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Indexes(indexes) => write!(
+                f,
+                "{}",
+                indexes
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(".")
+            ),
+            Self::Names(names) => write!(f, "{}", names.join(".")),
+        }
+    }
+}
+
+/// indexes when made of digits only, names otherwise
+pub(crate) fn parse_widget_path(path: &str) -> Result<WidgetPath, std::io::Error> {
+    let names = parse_path_names(path)?;
+    if names.is_empty() || names.iter().any(|x| x.is_empty()) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "empty widget path",
+        ));
+    }
+    if names.iter().all(|x| x.chars().all(|c| c.is_ascii_digit())) {
+        return Ok(WidgetPath::Indexes(parse_path_indexes(path)?));
+    }
+    Ok(WidgetPath::Names(names))
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum Output {
     Table,
@@ -1,6 +1,9 @@
 use clap::Parser;
 
-use crate::{list, show, tree, whereis, whois};
+use crate::{
+    affects, anim, conflicts, diff, grep, lint, list, merge_driver, patch, rename, report,
+    retarget, retime, select, show, textfit, transplant, tree, whereis, whois, wireframe,
+};
 
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Parser)] // requires `derive` feature
@@ -17,7 +20,55 @@ pub enum CLI {
     /// get full path indexes from path names
     #[command(name = "whereis")]
     WhereIs(whereis::Args),
+    /// get full path indexes and names of widgets matching a CSS-like selector
+    #[command(name = "select")]
+    Select(select::Args),
+    /// find widgets by their names, texts, localization keys, textures, fonts or styles
+    #[command(name = "grep")]
+    Grep(grep::Args),
     /// show json from widget name
     #[command(name = "show")]
     Show(show::Args),
+    /// draw widgets bounding boxes as .svg
+    #[command(name = "wireframe")]
+    Wireframe(wireframe::Args),
+    /// generate a self-contained .html report to browse widgets and anims
+    #[command(name = "report")]
+    Report(report::Args),
+    /// estimate whether text overflows its inkTextWidget
+    #[command(name = "text-fit")]
+    TextFit(textfit::Args),
+    /// scale, shift, clamp or snap anims timing, and write the .inkanim back
+    #[command(name = "retime")]
+    Retime(retime::Args),
+    /// point anims from one widget to another, and write the .inkanim back
+    #[command(name = "retarget")]
+    Retarget(retarget::Args),
+    /// extract, split or merge sequences across .inkanim
+    #[command(name = "anim")]
+    Anim(anim::Args),
+    /// copy a widget subtree into another .inkwidget, alongside its anims
+    #[command(name = "transplant")]
+    Transplant(transplant::Args),
+    /// apply declarative .toml patches to .inkwidget and .inkanim
+    #[command(name = "patch")]
+    Patch(patch::Args),
+    /// rename a widget or a sequence, listing every changed full path
+    #[command(name = "rename")]
+    Rename(rename::Args),
+    /// check widget and anim structural consistency
+    #[command(name = "lint")]
+    Lint(lint::Args),
+    /// list every anim affecting a widget, directly or through its ancestors
+    #[command(name = "affects")]
+    Affects(affects::Args),
+    /// find interpolators animating the same property at the same time
+    #[command(name = "conflicts")]
+    Conflicts(conflicts::Args),
+    /// compare two exports of the same .inkwidget or .inkanim
+    #[command(name = "diff")]
+    Diff(diff::Args),
+    /// structural three-way merge of .inkwidget or .inkanim, as a git merge driver
+    #[command(name = "merge-driver")]
+    MergeDriver(merge_driver::Args),
 }
@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use crate::args::Format;
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    /// .inkanim path
+    #[arg(short, long, value_name = "FILE")]
+    pub anim: PathBuf,
+
+    /// only analyze this sequence (all of them by default)
+    #[arg(short, long, value_name = "NAME")]
+    pub sequence: Option<String>,
+
+    /// only list real fights, skipping additive overlaps
+    #[arg(long)]
+    pub fights: bool,
+
+    /// optionally output as JSON, instead of table (default)
+    #[arg(value_enum, long, default_value_t = Format::Table)]
+    pub output: Format,
+}
@@ -0,0 +1,95 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::{File, anim::InkAnimAnimationLibraryResource};
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+use crate::args::Format;
+
+pub(crate) fn conflicts(args: Args, anim: File<InkAnimAnimationLibraryResource>) {
+    let anim = anim.resource();
+    if let Some(ref name) = args.sequence
+        && !anim.sequences.iter().any(|x| x.name() == name)
+    {
+        panic!("no sequence named {name}");
+    }
+    let conflicts = anim
+        .sequences
+        .iter()
+        .filter(|x| args.sequence.as_ref().is_none_or(|name| x.name() == name))
+        .flat_map(|sequence| {
+            sequence
+                .data
+                .conflicts()
+                .into_iter()
+                .filter(|x| !args.fights || x.is_fight())
+                .map(move |x| (sequence.name(), x))
+        })
+        .collect::<Vec<_>>();
+    match args.output {
+        Format::Table => {
+            if conflicts.is_empty() {
+                println!("no conflict found");
+                return;
+            }
+            let mut table = Table::new();
+            table.style = TableStyle::rounded();
+            table.add_row(Row::new(
+                [
+                    "sequence",
+                    "path",
+                    "property",
+                    "interpolators",
+                    "window",
+                    "overlap",
+                ]
+                .into_iter()
+                .map(|x| TableCell::builder(x).alignment(Alignment::Center).build())
+                .collect::<Vec<_>>(),
+            ));
+            for (sequence, conflict) in conflicts.iter() {
+                let [first, second] = conflict.interpolators;
+                table.add_row(Row::new(vec![
+                    TableCell::new(sequence),
+                    TableCell::new(
+                        conflict
+                            .path
+                            .iter()
+                            .map(|x| x.to_string())
+                            .collect::<Vec<_>>()
+                            .join("."),
+                    ),
+                    TableCell::new(&conflict.property),
+                    TableCell::new(format!("{first} {second}")),
+                    TableCell::builder(format!(
+                        "{}s → {}s",
+                        conflict.window.start, conflict.window.end
+                    ))
+                    .alignment(Alignment::Right)
+                    .build(),
+                    TableCell::new(if conflict.is_fight() {
+                        "⚔️ fight"
+                    } else {
+                        "➕ additive"
+                    }),
+                ]));
+            }
+            println!("{}", table.render());
+        }
+        Format::Json => {
+            let conflicts = conflicts
+                .iter()
+                .map(|(sequence, conflict)| {
+                    let mut value = serde_json::to_value(conflict).unwrap();
+                    value["sequence"] = serde_json::Value::from(*sequence);
+                    value
+                })
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string_pretty(&conflicts).unwrap());
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// compare two .inkwidget exports, widget by widget
+    #[command(name = "widget")]
+    Widget(Exports),
+    /// compare two .inkanim exports, sequence by sequence
+    #[command(name = "anim")]
+    Anim(Exports),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct Exports {
+    /// previous export path
+    #[arg(value_name = "OLD")]
+    pub old: PathBuf,
+
+    /// current export path
+    #[arg(value_name = "NEW")]
+    pub new: PathBuf,
+
+    /// optionally output as JSON or unified text, instead of table (default)
+    #[arg(value_enum, long, default_value_t = Format::Table)]
+    pub output: Format,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Text,
+}
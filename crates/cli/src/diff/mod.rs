@@ -0,0 +1,233 @@
+mod args;
+pub(crate) use args::{Args, Command};
+
+use args::Format;
+use inkanim_types::diff::{
+    AnimChange, Change, Location, PropertyChange, WidgetChange, diff_anims, diff_widgets,
+};
+use serde_json::Value;
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+use crate::read::{read_anim, read_widget};
+
+fn value(value: &Option<Value>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "(default)".to_string(),
+    }
+}
+
+fn dotted(location: &Location) -> (String, String) {
+    (
+        location.names.join("."),
+        location
+            .indexes
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+fn symbol(change: Change) -> &'static str {
+    match change {
+        Change::Added => "+",
+        Change::Removed => "-",
+        Change::Moved => "~",
+        Change::Changed => "@",
+    }
+}
+
+fn property_lines(properties: &[PropertyChange]) {
+    for property in properties {
+        if property.old.is_some() {
+            println!("-   {}: {}", property.property, value(&property.old));
+        }
+        if property.new.is_some() {
+            println!("+   {}: {}", property.property, value(&property.new));
+        }
+    }
+}
+
+fn widget_table(changes: &[WidgetChange]) {
+    let mut table = Table::new();
+    table.style = TableStyle::rounded();
+    table.add_row(Row::new(
+        ["change", "widget", "indexes", "property", "old", "new"]
+            .into_iter()
+            .map(|x| TableCell::builder(x).alignment(Alignment::Center).build())
+            .collect::<Vec<_>>(),
+    ));
+    for change in changes {
+        let (names, indexes) = dotted(change.location());
+        let (names, indexes) = match (change.change, change.old.as_ref()) {
+            (Change::Moved, Some(old)) => {
+                let (old_names, old_indexes) = dotted(old);
+                (
+                    if old_names == names {
+                        names
+                    } else {
+                        format!("{old_names} → {names}")
+                    },
+                    format!("{old_indexes} → {indexes}"),
+                )
+            }
+            _ => (names, indexes),
+        };
+        let row = |property: &str, old: String, new: String| {
+            Row::new(vec![
+                TableCell::new(change.change),
+                TableCell::new(format!("{names} ({})", change.classname)),
+                TableCell::new(&indexes),
+                TableCell::new(property),
+                TableCell::new(old),
+                TableCell::new(new),
+            ])
+        };
+        if change.properties.is_empty() {
+            table.add_row(row("", String::new(), String::new()));
+        }
+        for property in change.properties.iter() {
+            table.add_row(row(
+                &property.property,
+                value(&property.old),
+                value(&property.new),
+            ));
+        }
+    }
+    println!("{}", table.render());
+}
+
+fn widget_text(changes: &[WidgetChange]) {
+    for change in changes {
+        let (names, indexes) = dotted(change.location());
+        match (change.change, change.old.as_ref()) {
+            (Change::Moved, Some(old)) => {
+                let (old_names, old_indexes) = dotted(old);
+                println!(
+                    "{} {names} ({}) moved from {old_names} at {old_indexes} to {indexes}",
+                    symbol(change.change),
+                    change.classname
+                );
+            }
+            _ => println!(
+                "{} {names} ({}) at {indexes}",
+                symbol(change.change),
+                change.classname
+            ),
+        }
+        property_lines(&change.properties);
+    }
+}
+
+fn target(change: &AnimChange) -> String {
+    change
+        .target
+        .as_ref()
+        .map(|x| {
+            x.iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(".")
+        })
+        .unwrap_or_default()
+}
+
+fn anim_table(changes: &[AnimChange]) {
+    let mut table = Table::new();
+    table.style = TableStyle::rounded();
+    table.add_row(Row::new(
+        [
+            "change",
+            "sequence",
+            "definition",
+            "target",
+            "subject",
+            "property",
+            "old",
+            "new",
+        ]
+        .into_iter()
+        .map(|x| TableCell::builder(x).alignment(Alignment::Center).build())
+        .collect::<Vec<_>>(),
+    ));
+    for change in changes {
+        let target = target(change);
+        let row = |property: &str, old: String, new: String| {
+            Row::new(vec![
+                TableCell::new(change.change),
+                TableCell::new(&change.sequence),
+                TableCell::builder(change.definition.map(|x| x.to_string()).unwrap_or_default())
+                    .alignment(Alignment::Right)
+                    .build(),
+                TableCell::new(&target),
+                TableCell::new(&change.subject),
+                TableCell::new(property),
+                TableCell::new(old),
+                TableCell::new(new),
+            ])
+        };
+        if change.properties.is_empty() {
+            table.add_row(row("", String::new(), String::new()));
+        }
+        for property in change.properties.iter() {
+            table.add_row(row(
+                &property.property,
+                value(&property.old),
+                value(&property.new),
+            ));
+        }
+    }
+    println!("{}", table.render());
+}
+
+fn anim_text(changes: &[AnimChange]) {
+    for change in changes {
+        let location = match change.definition {
+            Some(definition) => format!(" definition {definition} at {}", target(change)),
+            None => String::new(),
+        };
+        println!(
+            "{} {}{location} {}",
+            symbol(change.change),
+            change.sequence,
+            change.subject
+        );
+        property_lines(&change.properties);
+    }
+}
+
+pub(crate) fn diff(args: Args) {
+    match args.command {
+        Command::Widget(args) => {
+            let old = read_widget(&args.old);
+            let new = read_widget(&args.new);
+            let changes = diff_widgets(&old.data.root_chunk, &new.data.root_chunk);
+            match args.output {
+                Format::Json => {
+                    println!("{}", serde_json::to_string_pretty(&changes).unwrap())
+                }
+                _ if changes.is_empty() => println!("no difference found"),
+                Format::Table => widget_table(&changes),
+                Format::Text => widget_text(&changes),
+            }
+        }
+        Command::Anim(args) => {
+            let old = read_anim(&args.old);
+            let new = read_anim(&args.new);
+            let changes = diff_anims(&old.data.root_chunk, &new.data.root_chunk);
+            match args.output {
+                Format::Json => {
+                    println!("{}", serde_json::to_string_pretty(&changes).unwrap())
+                }
+                _ if changes.is_empty() => println!("no difference found"),
+                Format::Table => anim_table(&changes),
+                Format::Text => anim_text(&changes),
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use inkanim_types::widget::SearchableField;
+use regex::Regex;
+
+use crate::args::{Format, parse_regex};
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    /// regular expression searched in widgets names, texts, localization keys,
+    /// texture parts and atlases, font families and style bindings
+    ///
+    /// e.g. "MainColors\.(Red|Blue)"
+    #[arg(value_parser = parse_regex, value_name = "PATTERN")]
+    pub pattern: Regex,
+
+    /// .inkwidget path
+    #[arg(short, long, value_name = "FILE")]
+    pub widget: PathBuf,
+
+    /// case insensitive search
+    #[arg(short, long)]
+    pub ignore_case: bool,
+
+    /// only search these fields (all of them by default)
+    #[arg(value_enum, short, long, value_name = "FIELD")]
+    pub field: Vec<SearchableField>,
+
+    /// optionally output as JSON, instead of table (default)
+    #[arg(value_enum, long, default_value_t = Format::Table)]
+    pub output: Format,
+}
@@ -0,0 +1,76 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::{File, widget::inkWidgetLibraryResource};
+use regex::RegexBuilder;
+use serde_json::json;
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+use crate::args::Format;
+
+pub(crate) fn grep(args: Args, widget: File<inkWidgetLibraryResource>) {
+    let widget = widget.resource();
+    let pattern = if args.ignore_case {
+        RegexBuilder::new(args.pattern.as_str())
+            .case_insensitive(true)
+            .build()
+            .expect("pattern already validated")
+    } else {
+        args.pattern.clone()
+    };
+    let hits = widget
+        .search(|x| pattern.is_match(x))
+        .into_iter()
+        .filter(|x| args.field.is_empty() || args.field.contains(&x.field))
+        .map(|hit| {
+            let names = widget
+                .unambiguous_path_names(&hit.node.indexes)
+                .unwrap_or(hit.node.names);
+            (hit.node.indexes, names, hit.field, hit.value)
+        })
+        .collect::<Vec<_>>();
+    match args.output {
+        Format::Table => {
+            if hits.is_empty() {
+                println!("no widget matches {}", args.pattern);
+                return;
+            }
+            let mut table = Table::new();
+            table.style = TableStyle::rounded();
+            table.add_row(Row::new(
+                ["indexes", "names", "field", "value"]
+                    .into_iter()
+                    .map(|x| TableCell::builder(x).alignment(Alignment::Center).build())
+                    .collect::<Vec<_>>(),
+            ));
+            for (indexes, names, field, value) in hits.iter() {
+                table.add_row(Row::new(vec![
+                    TableCell::new(
+                        indexes
+                            .iter()
+                            .map(|x| x.to_string())
+                            .collect::<Vec<_>>()
+                            .join("."),
+                    ),
+                    TableCell::new(names.join(".")),
+                    TableCell::new(field),
+                    TableCell::new(value),
+                ]));
+            }
+            println!("{}", table.render());
+        }
+        Format::Json => {
+            let hits = hits
+                .iter()
+                .map(|(indexes, names, field, value)| {
+                    json!({ "indexes": indexes, "names": names, "field": field, "value": value })
+                })
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string_pretty(&hits).unwrap());
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use crate::args::{Files, Format};
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    #[command(flatten)]
+    pub files: Files,
+
+    /// optionally output as JSON, instead of table (default)
+    #[arg(value_enum, long, default_value_t = Format::Table)]
+    pub output: Format,
+}
@@ -0,0 +1,68 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::{Lint, Severity, lint as check};
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+use crate::{
+    args::Format,
+    read::{anim_path, read_anim, read_widget},
+};
+
+fn table(lints: &[Lint]) {
+    if lints.is_empty() {
+        println!("no issue found");
+        return;
+    }
+    let mut table = Table::new();
+    table.style = TableStyle::rounded();
+    table.add_row(Row::new(
+        ["severity", "rule", "sequence", "definition", "message"]
+            .into_iter()
+            .map(|x| TableCell::builder(x).alignment(Alignment::Center).build())
+            .collect::<Vec<_>>(),
+    ));
+    for lint in lints {
+        table.add_row(Row::new(vec![
+            TableCell::new(match lint.severity {
+                Severity::Warning => "⚠️ warning",
+                Severity::Error => "❌ error",
+            }),
+            TableCell::new(lint.rule),
+            TableCell::new(lint.sequence.as_deref().unwrap_or_default()),
+            TableCell::builder(lint.definition.map(|x| x.to_string()).unwrap_or_default())
+                .alignment(Alignment::Right)
+                .build(),
+            TableCell::new(&lint.message),
+        ]));
+    }
+    println!("{}", table.render());
+}
+
+fn json(lints: &[Lint]) {
+    println!("{}", serde_json::to_string_pretty(lints).unwrap());
+}
+
+/// exits with code `1` when any error is found
+pub(crate) fn lint(args: Args) {
+    let widget = read_widget(&args.files.widget);
+    let anim = read_anim(
+        &args
+            .files
+            .anim
+            .clone()
+            .unwrap_or_else(|| anim_path(&args.files.widget)),
+    );
+    let lints = check(&widget.data.root_chunk, &anim.data.root_chunk);
+    match args.output {
+        Format::Table => table(&lints),
+        Format::Json => json(&lints),
+    }
+    if lints.iter().any(|x| x.severity == Severity::Error) {
+        std::process::exit(1);
+    }
+}
@@ -1,4 +1,4 @@
-use inkanim_types::anim::InkAnimInterpolatorType;
+use inkanim_types::{anim::InkAnimInterpolatorType, widget::Selector};
 
 use crate::args::{Files, Mode, OptionalPathIndexes};
 
@@ -11,6 +11,12 @@ pub struct Args {
     #[command(flatten)]
     pub path: OptionalPathIndexes,
 
+    /// optionally filter by widgets matching a CSS-like selector, instead of path indexes
+    ///
+    /// e.g. "inkTextWidget[name^=warning]"
+    #[arg(long, value_name = "SELECTOR", conflicts_with = "path")]
+    pub select: Option<Selector>,
+
     /// optionally filter by interpolation type
     #[arg(short, long)]
     pub r#type: Option<InkAnimInterpolatorType>,
@@ -21,12 +21,22 @@ pub(crate) fn list(
     widget: inkWidgetLibraryResource,
     anim: InkAnimAnimationLibraryResource,
 ) {
-    let filter_by_path = args.path.path;
+    let filter_by_paths = match (args.path.path, args.select) {
+        (Some(path), _) => Some(vec![path]),
+        (None, Some(selector)) => Some(
+            widget
+                .select(&selector)
+                .into_iter()
+                .map(|x| x.indexes)
+                .collect(),
+        ),
+        (None, None) => None,
+    };
     let filter_by_type = args.r#type;
     let duo = DualResources {
         widget,
         anim,
-        filter_by_path,
+        filter_by_paths,
         filter_by_type,
         show_path_names: args.show_path_names,
     };
@@ -59,7 +69,7 @@ fn into_table(value: DualResources) -> Vec<Table> {
         widget,
         anim,
         show_path_names,
-        filter_by_path,
+        filter_by_paths,
         ..
     } = value;
     let mut tables: Vec<Table> = Vec::with_capacity(anim.sequences.len());
@@ -141,10 +151,10 @@ fn into_table(value: DualResources) -> Vec<Table> {
                 Target::WithHandleId(infos) => Some(infos.clone().data.path),
                 Target::WithoutHandleId(_) => None,
             };
-            if let Some(ref filter) = filter_by_path
+            if let Some(ref filters) = filter_by_paths
                 && infos
                     .as_ref()
-                    .map(|x| !x.sibling_or_nested(filter))
+                    .map(|x| !filters.iter().any(|filter| x.sibling_or_nested(filter)))
                     .unwrap_or(true)
             {
                 continue;
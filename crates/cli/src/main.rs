@@ -1,37 +1,125 @@
 use clap::Parser;
 use cli::CLI;
 
+mod affects;
+mod anim;
 mod args;
 mod cli;
+mod conflicts;
+mod diff;
+mod grep;
+mod lint;
 mod list;
+mod merge_driver;
+mod patch;
 mod read;
+mod rename;
+mod report;
+mod retarget;
+mod retime;
+mod select;
 mod show;
+mod textfit;
+mod transplant;
 mod tree;
 mod whereis;
 mod whois;
+mod wireframe;
 
+use affects::affects;
+use anim::anim;
+use conflicts::conflicts;
+use diff::diff;
+use grep::grep;
+use lint::lint;
 use list::list;
-use read::read;
+use merge_driver::merge_driver;
+use patch::patch;
+use read::{read, read_anim, read_widget};
+use rename::rename;
+use report::report;
+use retarget::retarget;
+use retime::retime;
+use select::select;
 use show::show;
+use textfit::textfit;
+use transplant::transplant;
 use tree::tree;
 use whereis::whereis;
 use whois::whois;
+use wireframe::wireframe;
 
 fn main() {
-    let args = CLI::parse();
-    let files = match args {
-        CLI::List(list::Args { ref files, .. }) => files,
-        CLI::Tree(tree::Args { ref files, .. }) => files,
-        CLI::WhoIs(whois::Args { ref files, .. }) => files,
-        CLI::WhereIs(whereis::Args { ref files, .. }) => files,
-        CLI::Show(show::Args { ref files, .. }) => files,
-    };
-    let (widget, anim) = read(files);
-    match args {
-        CLI::List(args) => list(args, widget.resource(), anim.resource()),
-        CLI::Tree(args) => tree(args, widget),
-        CLI::WhoIs(args) => whois(args, widget.resource(), anim.resource()),
-        CLI::WhereIs(args) => whereis(args, widget.resource(), anim.resource()),
-        CLI::Show(args) => show(args, widget.resource()),
-    };
+    match CLI::parse() {
+        CLI::List(args) => {
+            let (widget, anim) = read(&args.files);
+            list(args, widget.resource(), anim.resource())
+        }
+        CLI::Tree(args) => {
+            let (widget, anim) = read(&args.files);
+            tree(args, widget, anim.resource())
+        }
+        CLI::WhoIs(args) => {
+            let (widget, anim) = read(&args.files);
+            whois(args, widget.resource(), anim.resource())
+        }
+        CLI::WhereIs(args) => {
+            let (widget, anim) = read(&args.files);
+            whereis(args, widget.resource(), anim.resource())
+        }
+        CLI::Show(args) => {
+            let (widget, _) = read(&args.files);
+            show(args, widget.resource())
+        }
+        CLI::Wireframe(args) => {
+            let (widget, _) = read(&args.files);
+            wireframe(args, widget.resource())
+        }
+        CLI::Report(args) => {
+            let (widget, anim) = read(&args.files);
+            report(args, widget, anim.resource())
+        }
+        CLI::TextFit(args) => {
+            let (widget, _) = read(&args.files);
+            textfit(args, widget.resource())
+        }
+        CLI::Retarget(args) => {
+            let (widget, anim) = read(&args.files);
+            retarget(args, widget.resource(), anim)
+        }
+        CLI::Rename(args) => {
+            let (widget, anim) = read(&args.files);
+            rename(args, widget, anim)
+        }
+        CLI::Affects(args) => {
+            let (widget, anim) = read(&args.files);
+            affects(args, widget.resource(), anim.resource())
+        }
+        // commands which only need the .inkanim
+        CLI::Retime(args) => {
+            let anim = read_anim(&args.anim);
+            retime(args, anim)
+        }
+        CLI::Conflicts(args) => {
+            let anim = read_anim(&args.anim);
+            conflicts(args, anim)
+        }
+        CLI::Anim(args) => anim(args),
+        // commands which only need the .inkwidget
+        CLI::Select(args) => {
+            let widget = read_widget(&args.widget);
+            select(args, widget)
+        }
+        CLI::Grep(args) => {
+            let widget = read_widget(&args.widget);
+            grep(args, widget)
+        }
+        // commands which need several .inkwidget
+        CLI::Transplant(args) => transplant(args),
+        CLI::Patch(args) => patch(args),
+        CLI::Diff(args) => diff(args),
+        CLI::MergeDriver(args) => merge_driver(args),
+        // commands which tolerate mismatching .inkwidget and .inkanim
+        CLI::Lint(args) => lint(args),
+    }
 }
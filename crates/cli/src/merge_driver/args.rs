@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    /// common ancestor version (git `%O`)
+    #[arg(value_name = "ANCESTOR")]
+    pub ancestor: PathBuf,
+
+    /// our version (git `%A`), overwritten with the merge result
+    #[arg(value_name = "CURRENT")]
+    pub current: PathBuf,
+
+    /// their version (git `%B`)
+    #[arg(value_name = "OTHER")]
+    pub other: PathBuf,
+
+    /// path name of the file being merged, for messages only (git `%P`)
+    #[arg(long, value_name = "PATH")]
+    pub name: Option<PathBuf>,
+}
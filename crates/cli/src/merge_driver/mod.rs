@@ -0,0 +1,56 @@
+mod args;
+pub(crate) use args::Args;
+
+use std::path::Path;
+
+use inkanim_types::{
+    File, anim::InkAnimAnimationLibraryResource, merge3, widget::inkWidgetLibraryResource,
+};
+use serde_json::Value;
+
+fn read(path: &Path) -> Value {
+    let json =
+        std::fs::read_to_string(path).unwrap_or_else(|e| panic!("read {}: {e}", path.display()));
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("parse {}: {e}", path.display()))
+}
+
+/// make sure the merge result still reads as a resource
+fn validate(merged: &Value) {
+    let valid = match merged["Data"]["RootChunk"]["$type"].as_str() {
+        Some("inkWidgetLibraryResource") => {
+            serde_json::from_value::<File<inkWidgetLibraryResource>>(merged.clone()).map(|_| ())
+        }
+        Some("inkanimAnimationLibraryResource") => {
+            serde_json::from_value::<File<InkAnimAnimationLibraryResource>>(merged.clone())
+                .map(|_| ())
+        }
+        other => panic!("unsupported resource type {other:?}"),
+    };
+    if let Err(e) = valid {
+        panic!("merge result is not a valid resource: {e}");
+    }
+}
+
+/// exits with code `1` on conflicts, leaving our side in place for each of them
+pub(crate) fn merge_driver(args: Args) {
+    let ancestor = read(&args.ancestor);
+    let current = read(&args.current);
+    let other = read(&args.other);
+    let (merged, conflicts) = merge3(&ancestor, &current, &other);
+    validate(&merged);
+    let json = serde_json::to_string_pretty(&merged).expect("serialize merge result");
+    std::fs::write(&args.current, json).expect("write merge result");
+    if conflicts.is_empty() {
+        return;
+    }
+    let name = args.name.as_ref().unwrap_or(&args.current);
+    eprintln!(
+        "{} conflict(s) merging {}, our side was kept:",
+        conflicts.len(),
+        name.display()
+    );
+    for conflict in conflicts.iter() {
+        eprintln!("  {conflict}");
+    }
+    std::process::exit(1);
+}
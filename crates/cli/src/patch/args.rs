@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use inkanim_types::Fidelity;
+
+use crate::args::Files;
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// apply a .toml patch file to .inkwidget and .inkanim
+    #[command(name = "apply")]
+    Apply(Apply),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct Apply {
+    /// .toml patch path
+    #[arg(long, value_name = "FILE")]
+    pub patch: PathBuf,
+
+    #[command(flatten)]
+    pub files: Files,
+
+    /// .inkwidget output path
+    ///
+    /// note: .inkanim gets written alongside, with "inkanim" instead of "inkwidget" in its name,
+    /// unless specified with `--out-anim`
+    #[arg(short, long, value_name = "FILE", required_unless_present = "dry_run")]
+    pub out: Option<PathBuf>,
+
+    /// optional .inkanim output path
+    #[arg(long, value_name = "FILE")]
+    pub out_anim: Option<PathBuf>,
+
+    /// only print the diff, without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// import-ready for WolvenKit (default), or trimmed for reading
+    #[arg(value_enum, long, default_value_t = Fidelity::WolvenKit)]
+    pub fidelity: Fidelity,
+}
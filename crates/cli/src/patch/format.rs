@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// declarative edits, written in TOML
+///
+/// widgets edits are applied first (in order), then interpolators, then sequences.
+/// every selector is resolved against the tree as patched so far.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Patch {
+    #[serde(default, rename = "widget")]
+    pub widgets: Vec<WidgetPatch>,
+    #[serde(default, rename = "interpolator")]
+    pub interpolators: Vec<InterpolatorPatch>,
+    #[serde(default, rename = "sequence")]
+    pub sequences: Vec<SequencePatch>,
+}
+
+/// `[[widget]]` table
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WidgetPatch {
+    /// widget path names
+    ///
+    /// e.g. "MAIN_CANVAS.Booting_Canvas"
+    pub select: String,
+    /// properties to overwrite, as named in WolvenKit JSON
+    ///
+    /// e.g. `set = { opacity = 0.5, layout.margin.left = 12 }`
+    #[serde(default)]
+    pub set: Map<String, Value>,
+    /// children to remove, by name
+    #[serde(default)]
+    pub remove: Vec<String>,
+    /// children to add, from WolvenKit JSON
+    #[serde(default)]
+    pub add: Vec<AddChild>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AddChild {
+    /// widget JSON path, relative to the patch file
+    ///
+    /// e.g. as output by `inkanim show --fidelity wolvenkit`
+    pub file: PathBuf,
+    /// position among children (last by default)
+    pub index: Option<usize>,
+}
+
+/// `[[interpolator]]` table
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InterpolatorPatch {
+    /// sequence name (every sequence by default)
+    pub sequence: Option<String>,
+    /// targeted widget path names
+    pub target: String,
+    /// only interpolators of this kind, as in `inkanim list --type`
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    /// only interpolator at this index, as in `inkanim list`
+    pub index: Option<usize>,
+    /// properties to overwrite, as named in WolvenKit JSON
+    ///
+    /// e.g. `set = { duration = 0.5, endValue.X = 2 }`
+    pub set: Map<String, Value>,
+}
+
+/// `[[sequence]]` table, same as `inkanim retime`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SequencePatch {
+    /// sequence name
+    pub select: String,
+    pub scale: Option<f32>,
+    pub stretch: Option<f32>,
+    pub shift: Option<f32>,
+    pub after: Option<f32>,
+    pub before: Option<f32>,
+    pub clamp: Option<f32>,
+    pub fps: Option<f32>,
+}
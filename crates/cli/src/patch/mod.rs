@@ -0,0 +1,468 @@
+mod args;
+mod format;
+pub(crate) use args::{Args, Command};
+
+use std::path::{Path, PathBuf};
+
+use clap::{CommandFactory, ValueEnum, error::ErrorKind};
+use inkanim_types::{
+    Fidelity, File, InkWrapper,
+    anim::{InkAnimAnimationLibraryResource, InkAnimInterpolatorType, Target},
+    widget::{EditError, Widget, WidgetTree, inkWidgetLibraryResource},
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use similar::TextDiff;
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+use crate::{
+    args::parse_path_names,
+    cli::CLI,
+    read::{anim_path, read},
+};
+use format::{InterpolatorPatch, Patch, SequencePatch, WidgetPatch};
+
+/// why a patch entry could not be applied
+#[derive(Debug)]
+pub(crate) enum PatchError {
+    /// widget path names no longer match: `missing` was not found
+    Selector {
+        selector: String,
+        missing: String,
+    },
+    /// no such property on the selected element
+    UnknownProperty {
+        selector: String,
+        key: String,
+    },
+    /// property set, but resulting element is invalid
+    InvalidValue {
+        selector: String,
+        reason: String,
+    },
+    /// no child with this name below the selected widget
+    NoChild {
+        selector: String,
+        name: String,
+    },
+    NoSequence(String),
+    /// no interpolator left after filtering
+    NoInterpolator {
+        target: String,
+    },
+    UnknownType(String),
+    Edit {
+        selector: String,
+        error: EditError,
+    },
+    File {
+        path: PathBuf,
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Selector { selector, missing } => {
+                write!(
+                    f,
+                    "selector \"{selector}\" no longer matches: no widget named \"{missing}\""
+                )
+            }
+            Self::UnknownProperty { selector, key } => {
+                write!(f, "\"{selector}\" has no property \"{key}\"")
+            }
+            Self::InvalidValue { selector, reason } => {
+                write!(f, "invalid value for \"{selector}\": {reason}")
+            }
+            Self::NoChild { selector, name } => {
+                write!(f, "\"{selector}\" has no child named \"{name}\"")
+            }
+            Self::NoSequence(name) => write!(f, "no sequence named \"{name}\""),
+            Self::NoInterpolator { target } => {
+                write!(f, "no interpolator matches target \"{target}\"")
+            }
+            Self::UnknownType(kind) => write!(f, "unknown interpolator type \"{kind}\""),
+            Self::Edit { selector, error } => write!(f, "cannot edit \"{selector}\": {error}"),
+            Self::File { path, reason } => write!(f, "cannot read {}: {reason}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+/// overwrite existing JSON properties, recursively
+///
+/// returns the first unknown key on failure.
+fn merge(target: &mut Value, patch: &Map<String, Value>, prefix: &str) -> Result<(), String> {
+    for (key, value) in patch {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        let Some(existing) = target.get_mut(key) else {
+            return Err(path);
+        };
+        match (existing, value) {
+            (existing @ Value::Object(_), Value::Object(patch)) => merge(existing, patch, &path)?,
+            // e.g. CName, or any WolvenKit wrapped value
+            (Value::Object(existing), value) if existing.contains_key("$value") => {
+                existing.insert("$value".to_string(), value.clone());
+            }
+            (existing, value) => *existing = value.clone(),
+        }
+    }
+    Ok(())
+}
+
+fn keys(patch: &Map<String, Value>) -> String {
+    patch.keys().cloned().collect::<Vec<_>>().join(", ")
+}
+
+/// overwrite properties of `Data` on handle-wrapped `data`
+fn set<T: Serialize + DeserializeOwned>(
+    data: &InkWrapper<T>,
+    patch: &Map<String, Value>,
+    selector: &str,
+) -> Result<InkWrapper<T>, PatchError> {
+    let mut value = Fidelity::WolvenKit
+        .scope(|| serde_json::to_value(data))
+        .expect("serialize to JSON");
+    merge(&mut value["Data"], patch, "").map_err(|key| PatchError::UnknownProperty {
+        selector: selector.to_string(),
+        key,
+    })?;
+    serde_json::from_value(value).map_err(|e| PatchError::InvalidValue {
+        selector: selector.to_string(),
+        reason: e.to_string(),
+    })
+}
+
+fn select(widget: &inkWidgetLibraryResource, selector: &str) -> Result<Vec<usize>, PatchError> {
+    let names = parse_path_names(selector).map_err(|e| PatchError::Selector {
+        selector: selector.to_string(),
+        missing: e.to_string(),
+    })?;
+    // e.g. "MAIN_CANVAS..Booting_Canvas"
+    if let Some(empty) = names.iter().find(|x| x.is_empty()) {
+        return Err(PatchError::Selector {
+            selector: selector.to_string(),
+            missing: empty.clone(),
+        });
+    }
+    let names = names.iter().map(|x| x.as_str()).collect::<Vec<_>>();
+    let (found, missing) = widget.get_partial_path_indexes(&names);
+    if found.len() != names.len() {
+        return Err(PatchError::Selector {
+            selector: selector.to_string(),
+            missing,
+        });
+    }
+    Ok(found)
+}
+
+fn widget_mut<'a>(
+    widget: &'a mut inkWidgetLibraryResource,
+    path: &[usize],
+) -> Result<&'a mut InkWrapper<Widget>, EditError> {
+    let (idx, parent) = path
+        .split_last()
+        .ok_or_else(|| EditError::NotFound(path.to_vec()))?;
+    widget
+        .root_chunk_mut()
+        .children_at_mut(parent)?
+        .get_mut(*idx)
+        .ok_or_else(|| EditError::NotFound(path.to_vec()))
+}
+
+struct Patcher<'a> {
+    widget: &'a mut inkWidgetLibraryResource,
+    anim: &'a mut InkAnimAnimationLibraryResource,
+    /// where files referenced in patch are relative to
+    dir: &'a Path,
+    /// what got changed, for each entry
+    log: Vec<(String, String)>,
+}
+
+impl Patcher<'_> {
+    fn widget(&mut self, patch: &WidgetPatch) -> Result<(), PatchError> {
+        let selector = patch.select.as_str();
+        let edit = |error| PatchError::Edit {
+            selector: selector.to_string(),
+            error,
+        };
+        let path = select(self.widget, selector)?;
+        if !patch.set.is_empty() {
+            let current = widget_mut(self.widget, &path).map_err(edit)?;
+            *current = set(current, &patch.set, selector)?;
+            self.log
+                .push((selector.to_string(), format!("set {}", keys(&patch.set))));
+        }
+        for name in patch.remove.iter() {
+            let idx = widget_mut(self.widget, &path)
+                .map_err(edit)?
+                .data
+                .as_compound()
+                .and_then(|x| {
+                    x.children_ref()
+                        .iter()
+                        .position(|x| x.data.name() == Some(name.as_str()))
+                })
+                .ok_or_else(|| PatchError::NoChild {
+                    selector: selector.to_string(),
+                    name: name.clone(),
+                })?;
+            let mut child = path.clone();
+            child.push(idx);
+            let (_, remap) = self.widget.remove(&child).map_err(edit)?;
+            let dropped = self.anim.remap_targets(&remap);
+            let mut sequences = dropped
+                .iter()
+                .map(|(idx, _)| self.anim.sequences[*idx].data.name.as_str())
+                .collect::<Vec<_>>();
+            sequences.dedup();
+            self.log.push((
+                selector.to_string(),
+                match dropped.len() {
+                    0 => format!("removed child {name}"),
+                    count => format!(
+                        "removed child {name} (dropped {count} definition(s) targeting it, from {})",
+                        sequences.join(", ")
+                    ),
+                },
+            ));
+        }
+        for add in patch.add.iter() {
+            let file = self.dir.join(&add.file);
+            let child = std::fs::read_to_string(&file)
+                .map_err(|e| e.to_string())
+                .and_then(|x| serde_json::from_str::<Value>(&x).map_err(|e| e.to_string()))
+                .and_then(|x| {
+                    // handle ID gets reallocated anyway
+                    let x = match x.get("HandleId") {
+                        Some(_) => x,
+                        None => serde_json::json!({ "HandleId": "0", "Data": x }),
+                    };
+                    serde_json::from_value::<InkWrapper<Widget>>(x).map_err(|e| e.to_string())
+                })
+                .map_err(|reason| PatchError::File {
+                    path: file.clone(),
+                    reason,
+                })?;
+            let (added, remap) = self.widget.graft(&path, add.index, &child).map_err(edit)?;
+            self.anim.remap_targets(&remap);
+            self.log.push((
+                selector.to_string(),
+                format!(
+                    "added child {} at index {}",
+                    child.data.name().unwrap_or_default(),
+                    added.last().expect("child index")
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn interpolator(&mut self, patch: &InterpolatorPatch) -> Result<(), PatchError> {
+        let path = select(self.widget, &patch.target)?;
+        let kind = patch
+            .kind
+            .as_deref()
+            .map(|x| {
+                InkAnimInterpolatorType::from_str(x, true)
+                    .map_err(|_| PatchError::UnknownType(x.to_string()))
+            })
+            .transpose()?;
+        if let Some(name) = patch.sequence.as_deref()
+            && !self.anim.sequences.iter().any(|x| x.name() == name)
+        {
+            return Err(PatchError::NoSequence(name.to_string()));
+        }
+        let mut count = 0;
+        for sequence in self.anim.sequences.iter_mut() {
+            if patch
+                .sequence
+                .as_deref()
+                .is_some_and(|x| x != sequence.name())
+            {
+                continue;
+            }
+            let sequence = &mut sequence.data;
+            for (definition, target) in sequence.definitions.iter_mut().zip(sequence.targets.iter())
+            {
+                if !matches!(target, Target::WithHandleId(x) if x.data.path == path) {
+                    continue;
+                }
+                for (idx, interpolator) in definition.data.interpolators.iter_mut().enumerate() {
+                    if patch.index.is_some_and(|x| x != idx)
+                        || kind.as_ref().is_some_and(|x| interpolator.data != *x)
+                    {
+                        continue;
+                    }
+                    *interpolator = set(interpolator, &patch.set, &patch.target)?;
+                    count += 1;
+                }
+            }
+        }
+        if count == 0 {
+            return Err(PatchError::NoInterpolator {
+                target: patch.target.clone(),
+            });
+        }
+        self.log.push((
+            patch.target.clone(),
+            format!("set {} on {count} interpolator(s)", keys(&patch.set)),
+        ));
+        Ok(())
+    }
+
+    fn sequence(&mut self, patch: &SequencePatch) -> Result<(), PatchError> {
+        for factor in [patch.scale, patch.stretch, patch.clamp, patch.fps]
+            .into_iter()
+            .flatten()
+        {
+            if factor <= 0. {
+                return Err(PatchError::InvalidValue {
+                    selector: patch.select.clone(),
+                    reason: format!("retiming values must be positive, got {factor}"),
+                });
+            }
+        }
+        let sequence = self
+            .anim
+            .sequences
+            .iter_mut()
+            .find(|x| x.name() == patch.select)
+            .ok_or_else(|| PatchError::NoSequence(patch.select.clone()))?;
+        let sequence = &mut sequence.data;
+        let before = sequence.duration();
+        if let Some(factor) = patch.scale {
+            sequence.scale(factor);
+        }
+        if let Some(duration) = patch.stretch {
+            sequence.stretch(duration);
+        }
+        if let Some(offset) = patch.shift {
+            let after = patch.after.unwrap_or(f32::NEG_INFINITY);
+            let before = patch.before.unwrap_or(f32::INFINITY);
+            sequence.shift(after..before, offset);
+        }
+        if let Some(duration) = patch.clamp {
+            sequence.clamp(duration);
+        }
+        if let Some(fps) = patch.fps {
+            sequence.snap(fps);
+        }
+        self.log.push((
+            patch.select.clone(),
+            format!("retimed from {before}s to {}s", sequence.duration()),
+        ));
+        Ok(())
+    }
+}
+
+fn diff(name: &str, before: &str, after: &str) {
+    if before == after {
+        return;
+    }
+    print!(
+        "{}",
+        TextDiff::from_lines(before, after)
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("a/{name}"), &format!("b/{name}"))
+    );
+}
+
+fn to_json<T: Serialize>(fidelity: Fidelity, file: &File<T>) -> String {
+    fidelity
+        .scope(|| serde_json::to_string_pretty(file))
+        .expect("serialize to JSON")
+}
+
+pub(crate) fn patch(args: Args) {
+    let Command::Apply(args) = args.command;
+    // validated before doing anything
+    let out = args.out.as_ref().map(|out| {
+        let out_anim = args.out_anim.clone().unwrap_or_else(|| anim_path(out));
+        if out_anim == *out {
+            CLI::command()
+                .error(
+                    ErrorKind::MissingRequiredArgument,
+                    "please specify .inkanim output path with --out-anim",
+                )
+                .exit();
+        }
+        (out.clone(), out_anim)
+    });
+    let source = std::fs::read_to_string(&args.patch).expect(".toml");
+    let patch: Patch = toml::from_str(&source).unwrap_or_else(|e| panic!("invalid patch: {e}"));
+    let (mut widget, mut anim) = read(&args.files);
+    let before = (
+        to_json(args.fidelity, &widget),
+        to_json(args.fidelity, &anim),
+    );
+    let mut patcher = Patcher {
+        widget: &mut widget.data.root_chunk,
+        anim: &mut anim.data.root_chunk,
+        dir: args.patch.parent().unwrap_or(Path::new(".")),
+        log: vec![],
+    };
+    let mut errors = vec![];
+    for entry in patch.widgets.iter() {
+        errors.extend(patcher.widget(entry).err());
+    }
+    for entry in patch.interpolators.iter() {
+        errors.extend(patcher.interpolator(entry).err());
+    }
+    for entry in patch.sequences.iter() {
+        errors.extend(patcher.sequence(entry).err());
+    }
+    let log = std::mem::take(&mut patcher.log);
+    if !errors.is_empty() {
+        for error in errors.iter() {
+            eprintln!("error: {error}");
+        }
+        eprintln!("{} error(s), nothing written", errors.len());
+        std::process::exit(1);
+    }
+
+    let mut table = Table::new();
+    table.style = TableStyle::rounded();
+    table.add_row(Row::new(vec![
+        TableCell::builder("selector")
+            .alignment(Alignment::Center)
+            .build(),
+        TableCell::builder("change")
+            .alignment(Alignment::Center)
+            .build(),
+    ]));
+    for (selector, change) in log {
+        table.add_row(Row::new(vec![
+            TableCell::new(selector),
+            TableCell::new(change),
+        ]));
+    }
+    let after = (
+        to_json(args.fidelity, &widget),
+        to_json(args.fidelity, &anim),
+    );
+    if args.dry_run {
+        diff("inkwidget", &before.0, &after.0);
+        diff("inkanim", &before.1, &after.1);
+        println!("{}", table.render());
+        return;
+    }
+    let (out, out_anim) = out.expect("output path");
+    std::fs::write(&out, after.0).expect("write .inkwidget");
+    std::fs::write(&out_anim, after.1).expect("write .inkanim");
+    println!("{}", table.render());
+    println!("patched widget written to {}", out.display());
+    println!("patched anim written to {}", out_anim.display());
+}
@@ -2,8 +2,32 @@ use inkanim_types::{
     File, anim::InkAnimAnimationLibraryResource, widget::inkWidgetLibraryResource,
 };
 
+use std::path::{Path, PathBuf};
+
 use crate::args::Files;
 
+/// .inkanim path alongside .inkwidget, when left unspecified
+pub fn anim_path(widget: &Path) -> PathBuf {
+    widget.with_file_name(
+        widget
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .replace("inkwidget", "inkanim"),
+    )
+}
+
+pub fn read_anim(path: &Path) -> File<InkAnimAnimationLibraryResource> {
+    let anim_json_export = std::fs::read_to_string(path).expect(".inkanim");
+    serde_json::from_str::<File<InkAnimAnimationLibraryResource>>(&anim_json_export).unwrap()
+}
+
+pub fn read_widget(path: &Path) -> File<inkWidgetLibraryResource> {
+    let widget_json_export = std::fs::read_to_string(path).expect(".inkwidget");
+    serde_json::from_str::<File<inkWidgetLibraryResource>>(&widget_json_export).unwrap()
+}
+
+/// read both .inkwidget and .inkanim, which must have as many library items as sequences
 pub fn read(
     args: &Files,
 ) -> (
@@ -11,24 +35,10 @@ pub fn read(
     File<InkAnimAnimationLibraryResource>,
 ) {
     let widget_json_path = args.widget.clone();
-    let anim_json_path = args.anim.clone().unwrap_or_else(|| {
-        let path = args.widget.clone();
-        path.with_file_name(
-            args.widget
-                .file_name()
-                .unwrap()
-                .to_string_lossy()
-                .replace("inkwidget", "inkanim"),
-        )
-    });
-
-    let widget_json_export = std::fs::read_to_string(widget_json_path).expect(".inkwidget");
-    let anim_json_export = std::fs::read_to_string(anim_json_path).expect(".inkanim");
-
-    let widget_resource =
-        serde_json::from_str::<File<inkWidgetLibraryResource>>(&widget_json_export).unwrap();
-    let anim_resource =
-        serde_json::from_str::<File<InkAnimAnimationLibraryResource>>(&anim_json_export).unwrap();
+    let anim_json_path = args.anim.clone().unwrap_or_else(|| anim_path(&args.widget));
+
+    let widget_resource = read_widget(&widget_json_path);
+    let anim_resource = read_anim(&anim_json_path);
 
     let widget_chunk = &widget_resource.data.root_chunk;
     let anim_chunk = &anim_resource.data.root_chunk;
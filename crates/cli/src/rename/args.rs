@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use inkanim_types::Fidelity;
+
+use crate::args::{Files, Mode, WidgetPath, parse_widget_path};
+
+#[derive(clap::Args, Debug)]
+#[command(group = clap::ArgGroup::new("renamed").required(true))]
+pub struct Args {
+    #[command(flatten)]
+    pub files: Files,
+
+    /// widget to rename, by path names or indexes
+    ///
+    /// e.g. "MAIN_CANVAS.Booting_Canvas"
+    #[arg(short, long, value_parser = parse_widget_path, value_name = "PATH", group = "renamed")]
+    pub path: Option<WidgetPath>,
+
+    /// sequence to rename
+    #[arg(short, long, value_name = "NAME", group = "renamed")]
+    pub sequence: Option<String>,
+
+    /// new name
+    #[arg(long, value_name = "NAME")]
+    pub to: String,
+
+    /// output path: .inkwidget when renaming a widget, .inkanim when renaming a sequence
+    #[arg(short, long, value_name = "FILE")]
+    pub out: PathBuf,
+
+    /// import-ready for WolvenKit (default), or trimmed for reading
+    #[arg(value_enum, long, default_value_t = Fidelity::WolvenKit)]
+    pub fidelity: Fidelity,
+
+    #[command(flatten)]
+    pub mode: Mode,
+}
@@ -0,0 +1,108 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::{
+    File, anim::InkAnimAnimationLibraryResource, widget::inkWidgetLibraryResource,
+};
+use serde::Serialize;
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+use crate::args::Output;
+
+/// full path names, before and after
+#[derive(Serialize)]
+struct Renamed {
+    old: String,
+    new: String,
+}
+
+fn table(renamed: &[Renamed]) {
+    let mut table = Table::new();
+    table.style = TableStyle::rounded();
+    table.add_row(Row::new(vec![
+        TableCell::builder("old")
+            .alignment(Alignment::Center)
+            .build(),
+        TableCell::builder("new")
+            .alignment(Alignment::Center)
+            .build(),
+    ]));
+    for Renamed { old, new } in renamed {
+        table.add_row(Row::new(vec![TableCell::new(old), TableCell::new(new)]));
+    }
+    println!("{}", table.render());
+}
+
+fn json(renamed: &[Renamed]) {
+    println!("{}", serde_json::to_string_pretty(renamed).unwrap());
+}
+
+/// as passed to `GetWidget` or `PlayLibraryAnimation`
+fn reds(renamed: &[Renamed]) {
+    for Renamed { old, new } in renamed {
+        println!(
+            "n\"{}\" => n\"{}\"",
+            old.replace('.', "/"),
+            new.replace('.', "/")
+        );
+    }
+}
+
+pub(crate) fn rename(
+    args: Args,
+    mut widget: File<inkWidgetLibraryResource>,
+    mut anim: File<InkAnimAnimationLibraryResource>,
+) {
+    let (renamed, json_out, kind) = if let Some(path) = &args.path {
+        let indexes = path
+            .resolve(&widget.data.root_chunk)
+            .unwrap_or_else(|| panic!("couldn't find widget {path}"));
+        let renamed = widget
+            .data
+            .root_chunk
+            .rename(&indexes, &args.to)
+            .unwrap_or_else(|e| panic!("{e}"))
+            .into_iter()
+            .map(|(old, new)| Renamed {
+                old: old.join("."),
+                new: new.join("."),
+            })
+            .collect::<Vec<_>>();
+        let out = args
+            .fidelity
+            .scope(|| serde_json::to_string_pretty(&widget))
+            .expect("serialize .inkwidget");
+        (renamed, out, "widget")
+    } else {
+        let from = args.sequence.as_deref().expect("sequence name");
+        anim.data
+            .root_chunk
+            .rename(from, &args.to)
+            .unwrap_or_else(|e| panic!("{e}"));
+        let out = args
+            .fidelity
+            .scope(|| serde_json::to_string_pretty(&anim))
+            .expect("serialize .inkanim");
+        (
+            vec![Renamed {
+                old: from.to_string(),
+                new: args.to.clone(),
+            }],
+            out,
+            "anim",
+        )
+    };
+    std::fs::write(&args.out, json_out).expect("write output");
+    match args.mode.output {
+        Output::Table => table(&renamed),
+        Output::Json => json(&renamed),
+        Output::Reds => reds(&renamed),
+    }
+    if !matches!(args.mode.output, Output::Json) {
+        println!("renamed {kind} written to {}", args.out.display());
+    }
+}
@@ -0,0 +1,117 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::{
+    File,
+    anim::{InkAnimAnimationLibraryResource, Target},
+    widget::{Classname, Walk, inkWidgetLibraryResource},
+};
+use serde_json::{Value, json};
+
+/// page skeleton, data gets injected in place of this marker
+const DATA_MARKER: &str = "/* DATA */";
+const TEMPLATE: &str = include_str!("report.html");
+
+fn dotted(indexes: &[usize]) -> String {
+    indexes
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn widgets(widget: &inkWidgetLibraryResource) -> Vec<Value> {
+    widget
+        .walk()
+        .into_iter()
+        .map(|node| {
+            let mut properties =
+                serde_json::to_value(&node.widget.data).expect("serialize back to JSON");
+            if let Some(properties) = properties.as_object_mut() {
+                properties.remove("children");
+            }
+            json!({
+                "path": dotted(&node.indexes),
+                "names": node.names,
+                "classname": node.widget.data.classname(),
+                "handle": node.widget.handle_id,
+                "leaf": node.widget.data.is_leaf(),
+                "properties": properties,
+            })
+        })
+        .collect()
+}
+
+fn sequences(anim: &InkAnimAnimationLibraryResource) -> Vec<Value> {
+    anim.sequences
+        .iter()
+        .map(|sequence| {
+            let definitions = sequence
+                .data
+                .definitions
+                .iter()
+                .enumerate()
+                .map(|(idx, definition)| {
+                    let target = match sequence.data.targets.get(idx) {
+                        Some(Target::WithHandleId(handle)) => Some(dotted(&handle.data.path)),
+                        _ => None,
+                    };
+                    let interpolators = definition
+                        .data
+                        .interpolators
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, interpolator)| {
+                            json!({
+                                "index": idx,
+                                "handle": interpolator.handle_id,
+                                "kind": interpolator.data.as_short_display(),
+                                "emoji": interpolator.data.as_emoji(),
+                                "starts": interpolator.data.starts(),
+                                "ends": interpolator.data.ends(),
+                                "transformation": interpolator.data.transformation().to_string(),
+                                "interpolation": format!(
+                                    "{}.{}",
+                                    interpolator.data.r#type(),
+                                    interpolator.data.mode()
+                                ),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    json!({
+                        "index": idx,
+                        "handle": definition.handle_id,
+                        "target": target,
+                        "interpolators": interpolators,
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({
+                "name": sequence.name(),
+                "handle": sequence.handle_id,
+                "definitions": definitions,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn report(
+    args: Args,
+    widget: File<inkWidgetLibraryResource>,
+    anim: InkAnimAnimationLibraryResource,
+) {
+    let header = widget.header.to_string();
+    let widget = widget.resource();
+    let data = json!({
+        "header": header,
+        "widgets": widgets(&widget),
+        "sequences": sequences(&anim),
+    });
+    let data = serde_json::to_string(&data)
+        .expect("serialize report data")
+        // prevent any name from closing the inline script
+        .replace("</", "<\\/");
+    let html = TEMPLATE.replace(DATA_MARKER, &data);
+    std::fs::write(&args.out, html).expect("write .html");
+    println!("report written to {}", args.out.display());
+}
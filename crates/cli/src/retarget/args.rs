@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+use inkanim_types::Fidelity;
+
+use crate::args::{Files, WidgetPath, parse_widget_path};
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    #[command(flatten)]
+    pub files: Files,
+
+    /// widget currently targeted, by path names or indexes
+    ///
+    /// e.g. "main_canvas.Arrival" or "1.3"
+    #[arg(long, value_parser = parse_widget_path, value_name = "PATH")]
+    pub from: WidgetPath,
+
+    /// widget to target instead, by path names or indexes
+    ///
+    /// targets nested below `--from` keep their relative path below `--to`
+    #[arg(long, value_parser = parse_widget_path, value_name = "PATH")]
+    pub to: WidgetPath,
+
+    /// .inkanim output path
+    #[arg(short, long, value_name = "FILE")]
+    pub out: PathBuf,
+
+    /// import-ready for WolvenKit (default), or trimmed for reading
+    #[arg(value_enum, long, default_value_t = Fidelity::WolvenKit)]
+    pub fidelity: Fidelity,
+}
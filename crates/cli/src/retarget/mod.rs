@@ -0,0 +1,52 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::{
+    File, anim::InkAnimAnimationLibraryResource, widget::inkWidgetLibraryResource,
+};
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+pub(crate) fn retarget(
+    args: Args,
+    widget: inkWidgetLibraryResource,
+    mut file: File<InkAnimAnimationLibraryResource>,
+) {
+    let from = args
+        .from
+        .resolve(&widget)
+        .unwrap_or_else(|| panic!("couldn't find widget {}", args.from));
+    let to = args
+        .to
+        .resolve(&widget)
+        .unwrap_or_else(|| panic!("couldn't find widget {}", args.to));
+    let changed = file.data.root_chunk.retarget(&from, &to);
+    let mut table = Table::new();
+    table.style = TableStyle::rounded();
+    table.add_row(Row::new(vec![
+        TableCell::builder("sequence")
+            .alignment(Alignment::Center)
+            .build(),
+        TableCell::builder("definitions changed")
+            .alignment(Alignment::Center)
+            .build(),
+    ]));
+    for (sequence, count) in file.data.root_chunk.sequences.iter().zip(changed) {
+        table.add_row(Row::new(vec![
+            TableCell::new(sequence.name()),
+            TableCell::builder(count)
+                .alignment(Alignment::Right)
+                .build(),
+        ]));
+    }
+    let json = args
+        .fidelity
+        .scope(|| serde_json::to_string_pretty(&file))
+        .expect("serialize .inkanim");
+    std::fs::write(&args.out, json).expect("write .inkanim");
+    println!("{}", table.render());
+    println!("retargeted anim written to {}", args.out.display());
+}
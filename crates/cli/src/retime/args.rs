@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use inkanim_types::Fidelity;
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    /// .inkanim path
+    #[arg(short, long, value_name = "FILE")]
+    pub anim: PathBuf,
+
+    /// only retime this sequence (all of them by default)
+    #[arg(short, long, value_name = "NAME")]
+    pub sequence: Option<String>,
+
+    /// multiply every start and duration
+    ///
+    /// e.g. "0.8" to play 20% faster
+    #[arg(long, value_name = "FACTOR", conflicts_with = "stretch")]
+    pub scale: Option<f32>,
+
+    /// scale to this total duration, in seconds
+    #[arg(long, value_name = "SECONDS")]
+    pub stretch: Option<f32>,
+
+    /// delay by this many seconds (negative to bring forward)
+    #[arg(long, value_name = "SECONDS", allow_negative_numbers = true)]
+    pub shift: Option<f32>,
+
+    /// only shift what starts at or after this time, in seconds
+    #[arg(long, value_name = "SECONDS", requires = "shift")]
+    pub after: Option<f32>,
+
+    /// only shift what starts strictly before this time, in seconds
+    #[arg(long, value_name = "SECONDS", requires = "shift")]
+    pub before: Option<f32>,
+
+    /// cut everything past this total duration, in seconds
+    #[arg(long, value_name = "SECONDS")]
+    pub clamp: Option<f32>,
+
+    /// round every start and end to the nearest frame at this frame rate
+    #[arg(long, value_name = "FPS")]
+    pub fps: Option<f32>,
+
+    /// .inkanim output path
+    #[arg(short, long, value_name = "FILE")]
+    pub out: PathBuf,
+
+    /// import-ready for WolvenKit (default), or trimmed for reading
+    #[arg(value_enum, long, default_value_t = Fidelity::WolvenKit)]
+    pub fidelity: Fidelity,
+}
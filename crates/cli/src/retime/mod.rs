@@ -0,0 +1,82 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::{File, anim::InkAnimAnimationLibraryResource};
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+pub(crate) fn retime(args: Args, mut file: File<InkAnimAnimationLibraryResource>) {
+    for factor in [args.scale, args.stretch, args.clamp, args.fps]
+        .into_iter()
+        .flatten()
+    {
+        if factor <= 0. {
+            panic!("retiming values must be positive, got {factor}");
+        }
+    }
+    let mut table = Table::new();
+    table.style = TableStyle::rounded();
+    table.add_row(Row::new(vec![
+        TableCell::builder("sequence")
+            .alignment(Alignment::Center)
+            .build(),
+        TableCell::builder("before")
+            .alignment(Alignment::Center)
+            .build(),
+        TableCell::builder("after")
+            .alignment(Alignment::Center)
+            .build(),
+    ]));
+    let mut found = false;
+    for sequence in file.data.root_chunk.sequences.iter_mut() {
+        if args.sequence.as_ref().is_some_and(|x| x != sequence.name()) {
+            continue;
+        }
+        found = true;
+        let name = sequence.name().to_string();
+        let sequence = &mut sequence.data;
+        let before = sequence.duration();
+        if let Some(factor) = args.scale {
+            sequence.scale(factor);
+        }
+        if let Some(duration) = args.stretch {
+            sequence.stretch(duration);
+        }
+        if let Some(offset) = args.shift {
+            let after = args.after.unwrap_or(f32::NEG_INFINITY);
+            let before = args.before.unwrap_or(f32::INFINITY);
+            sequence.shift(after..before, offset);
+        }
+        if let Some(duration) = args.clamp {
+            sequence.clamp(duration);
+        }
+        if let Some(fps) = args.fps {
+            sequence.snap(fps);
+        }
+        table.add_row(Row::new(vec![
+            TableCell::new(name),
+            TableCell::builder(format!("{before}s"))
+                .alignment(Alignment::Right)
+                .build(),
+            TableCell::builder(format!("{}s", sequence.duration()))
+                .alignment(Alignment::Right)
+                .build(),
+        ]));
+    }
+    if !found {
+        panic!(
+            "no sequence named {}",
+            args.sequence.as_deref().unwrap_or_default()
+        );
+    }
+    let json = args
+        .fidelity
+        .scope(|| serde_json::to_string_pretty(&file))
+        .expect("serialize .inkanim");
+    std::fs::write(&args.out, json).expect("write .inkanim");
+    println!("{}", table.render());
+    println!("retimed anim written to {}", args.out.display());
+}
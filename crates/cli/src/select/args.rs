@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use inkanim_types::widget::Selector;
+
+use crate::args::Format;
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    /// CSS-like selector
+    ///
+    /// e.g. "inkCanvasWidget > inkTextWidget[name^=warning][visible=false]"
+    #[arg(value_name = "SELECTOR")]
+    pub selector: Selector,
+
+    /// .inkwidget path
+    #[arg(short, long, value_name = "FILE")]
+    pub widget: PathBuf,
+
+    /// optionally output as JSON, instead of table (default)
+    #[arg(value_enum, long, default_value_t = Format::Table)]
+    pub output: Format,
+}
@@ -0,0 +1,68 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::{
+    File,
+    widget::{Classname, inkWidgetLibraryResource},
+};
+use serde_json::json;
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+use crate::args::Format;
+
+pub(crate) fn select(args: Args, widget: File<inkWidgetLibraryResource>) {
+    let widget = widget.resource();
+    let selected = widget
+        .select(&args.selector)
+        .into_iter()
+        .map(|node| {
+            let names = widget
+                .unambiguous_path_names(&node.indexes)
+                .unwrap_or(node.names);
+            (node.indexes, names, node.widget.data.classname())
+        })
+        .collect::<Vec<_>>();
+    match args.output {
+        Format::Table => {
+            if selected.is_empty() {
+                println!("no widget matches {}", args.selector);
+                return;
+            }
+            let mut table = Table::new();
+            table.style = TableStyle::rounded();
+            table.add_row(Row::new(
+                ["indexes", "names", "class"]
+                    .into_iter()
+                    .map(|x| TableCell::builder(x).alignment(Alignment::Center).build())
+                    .collect::<Vec<_>>(),
+            ));
+            for (indexes, names, classname) in selected.iter() {
+                table.add_row(Row::new(vec![
+                    TableCell::new(
+                        indexes
+                            .iter()
+                            .map(|x| x.to_string())
+                            .collect::<Vec<_>>()
+                            .join("."),
+                    ),
+                    TableCell::new(names.join(".")),
+                    TableCell::new(classname),
+                ]));
+            }
+            println!("{}", table.render());
+        }
+        Format::Json => {
+            let selected = selected
+                .iter()
+                .map(|(indexes, names, classname)| {
+                    json!({ "indexes": indexes, "names": names, "class": classname })
+                })
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string_pretty(&selected).unwrap());
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use inkanim_types::Fidelity;
+
 use crate::args::{Files, PathNames};
 
 #[derive(clap::Args, Debug)]
@@ -8,4 +10,8 @@ pub struct Args {
 
     #[command(flatten)]
     pub names: PathNames,
+
+    /// trimmed for reading, or wolvenkit to paste back into WolvenKit
+    #[arg(value_enum, long, default_value_t = Fidelity::Trimmed)]
+    pub fidelity: Fidelity,
 }
@@ -1,38 +1,34 @@
 mod args;
 pub(crate) use args::Args;
 
-use inkanim_types::widget::{ByName, Widget, WidgetTree, inkWidgetLibraryResource};
+use inkanim_types::widget::{WidgetTree, inkWidgetLibraryResource};
 
 pub(crate) fn show(args: Args, widget: inkWidgetLibraryResource) {
-    let names = args
-        .names
-        .path
-        .iter()
-        .map(|x| x.as_str())
-        .collect::<Vec<_>>();
-    if names.is_empty() {
-        panic!("please specify widget path names");
-    }
-    let (found, name) = widget.get_partial_path_indexes(&names);
-    if found.len() != names.len() {
-        panic!(
-            "could not find {name} at {}",
-            found
+    let names = args.names.resolve(&widget);
+    let names = names.iter().map(|x| x.as_str()).collect::<Vec<_>>();
+    let found = widget
+        .get_path_indexes(&names)
+        .expect("already resolved above");
+    let candidates = widget.find_path_indexes(&names);
+    if candidates.len() > 1 {
+        eprintln!(
+            "{} widgets share this path, showing the first one among:\n{}",
+            candidates.len(),
+            candidates
                 .iter()
-                .cloned()
-                .map(|x| x.to_string())
+                .filter_map(|x| widget.unambiguous_path_names(x))
+                .map(|x| x.join("."))
                 .collect::<Vec<_>>()
-                .join(" . ")
-        )
-    }
-    let mut widget = Widget::inkCanvasWidget(widget.root_chunk().root_widget.data.clone());
-    let mut indexes = Vec::with_capacity(names.len());
-    for (idx, name) in names.iter().enumerate() {
-        indexes.push(idx);
-        (_, widget) = widget.by_name(name).expect("already validated above");
+                .join("\n")
+        );
     }
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&widget).expect("serialize back to JSON")
-    );
+    let widget = &widget
+        .widget_at(&found)
+        .expect("already validated above")
+        .data;
+    let json = args
+        .fidelity
+        .scope(|| serde_json::to_string_pretty(&widget))
+        .expect("serialize back to JSON");
+    println!("{json}");
 }
@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use crate::args::{Files, PathNames};
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    #[command(flatten)]
+    pub files: Files,
+
+    #[command(flatten)]
+    pub names: PathNames,
+
+    /// font metrics path (.ttf or .otf)
+    #[arg(short, long, value_name = "FILE")]
+    pub font: PathBuf,
+
+    /// optionally measure this text instead of the widget's own
+    ///
+    /// e.g. a localized string
+    #[arg(short, long)]
+    pub text: Option<String>,
+}
@@ -0,0 +1,130 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::widget::{
+    InkWidget, Widget, WidgetTree, font::textOverflowPolicy, geometry::Geometry,
+    inkWidgetLibraryResource, text::FontMetrics,
+};
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+const POLICIES: [textOverflowPolicy; 6] = [
+    textOverflowPolicy::None,
+    textOverflowPolicy::DotsEnd,
+    textOverflowPolicy::DotsEndLastLine,
+    textOverflowPolicy::AutoScroll,
+    textOverflowPolicy::PingPongScroll,
+    textOverflowPolicy::AdjustToSize,
+];
+
+/// font metrics read from a TrueType or OpenType font
+struct TrueType<'a> {
+    face: ttf_parser::Face<'a>,
+    units: f32,
+}
+
+impl FontMetrics for TrueType<'_> {
+    fn advance(&self, c: char) -> f32 {
+        self.face
+            .glyph_index(c)
+            .and_then(|x| self.face.glyph_hor_advance(x))
+            // missing glyphs usually fall back onto another font
+            .map(|x| x as f32 / self.units)
+            .unwrap_or(0.5)
+    }
+
+    fn kerning(&self, left: char, right: char) -> f32 {
+        let (Some(left), Some(right)) = (self.face.glyph_index(left), self.face.glyph_index(right))
+        else {
+            return 0.;
+        };
+        self.face
+            .tables()
+            .kern
+            .and_then(|kern| {
+                kern.subtables
+                    .into_iter()
+                    .filter(|x| x.horizontal && !x.variable)
+                    .find_map(|x| x.glyphs_kerning(left, right))
+            })
+            .map(|x| x as f32 / self.units)
+            .unwrap_or(0.)
+    }
+
+    fn line_height(&self) -> f32 {
+        (self.face.ascender() - self.face.descender() + self.face.line_gap()) as f32 / self.units
+    }
+}
+
+pub(crate) fn textfit(args: Args, widget: inkWidgetLibraryResource) {
+    let names = args.names.resolve(&widget);
+    let found = widget
+        .get_path_indexes(&names.iter().map(|x| x.as_str()).collect::<Vec<_>>())
+        .expect("already resolved above");
+    let boxes = widget.bounding_boxes();
+    let (node, bounds) = boxes
+        .iter()
+        .find(|(node, _)| node.indexes == found)
+        .unwrap_or_else(|| panic!("could not find {}", names.join(" . ")));
+    let Widget::inkTextWidget(text_widget) = &node.widget.data else {
+        panic!("{} is not an inkTextWidget", names.join(" . "));
+    };
+    let mut bounds = *bounds;
+    if text_widget.fit_to_content() {
+        // box grows along with its text, so only its parent can constrain it
+        if let Some((_, parent)) = boxes
+            .iter()
+            .find(|(x, _)| x.indexes == node.indexes[..node.indexes.len() - 1])
+        {
+            println!("fit to content: measuring against parent box");
+            bounds = *parent;
+        }
+    }
+
+    let data = std::fs::read(&args.font).expect("font file");
+    let face = ttf_parser::Face::parse(&data, 0).expect("valid .ttf or .otf");
+    let metrics = TrueType {
+        units: face.units_per_em() as f32,
+        face,
+    };
+
+    let text = args.text.unwrap_or_else(|| text_widget.text.clone());
+    let layout = text_widget.layout_text(&text, &metrics, bounds.width);
+    println!(
+        "box: {} x {}\ntext: {} x {} (font size: {}, {} line(s))\n{}",
+        bounds.width,
+        bounds.height,
+        layout.width,
+        layout.height,
+        text_widget.font_size,
+        layout.lines.len(),
+        layout.lines.join("\n"),
+    );
+
+    let mut table = Table::new();
+    table.style = TableStyle::rounded();
+    table.add_row(Row::new(vec![
+        TableCell::builder("policy")
+            .alignment(Alignment::Center)
+            .build(),
+        TableCell::builder("outcome")
+            .alignment(Alignment::Center)
+            .build(),
+    ]));
+    for policy in POLICIES {
+        let fit = text_widget.fit(&text, &metrics, (bounds.width, bounds.height), &policy);
+        let current = if policy == text_widget.text_overflow_policy {
+            " (current)"
+        } else {
+            ""
+        };
+        table.add_row(Row::new(vec![
+            TableCell::new(format!("{policy:?}{current}")),
+            TableCell::new(fit),
+        ]));
+    }
+    println!("{}", table.render());
+}
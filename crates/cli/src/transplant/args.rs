@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use inkanim_types::Fidelity;
+
+use crate::args::{WidgetPath, parse_widget_path};
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    /// source .inkwidget path
+    #[arg(long, value_name = "FILE")]
+    pub from: PathBuf,
+
+    /// optional source .inkanim path (defaults to the same path as source .inkwidget)
+    #[arg(long, value_name = "FILE")]
+    pub from_anim: Option<PathBuf>,
+
+    /// widget to copy (alongside its descendants) in source, by path names or indexes
+    #[arg(long, value_parser = parse_widget_path, value_name = "PATH")]
+    pub subtree: WidgetPath,
+
+    /// destination .inkwidget path
+    #[arg(long, value_name = "FILE")]
+    pub into: PathBuf,
+
+    /// optional destination .inkanim path (defaults to the same path as destination .inkwidget)
+    #[arg(long, value_name = "FILE")]
+    pub into_anim: Option<PathBuf>,
+
+    /// parent widget in destination, by path names or indexes (root widget by default)
+    #[arg(long, value_parser = parse_widget_path, value_name = "PATH")]
+    pub at: Option<WidgetPath>,
+
+    /// position among parent's children (last by default)
+    #[arg(long, value_name = "INDEX")]
+    pub index: Option<usize>,
+
+    /// .inkwidget output path
+    ///
+    /// note: .inkanim gets written alongside, with "inkanim" instead of "inkwidget" in its name,
+    /// unless specified with `--out-anim`
+    #[arg(short, long, value_name = "FILE")]
+    pub out: PathBuf,
+
+    /// optional .inkanim output path
+    #[arg(long, value_name = "FILE")]
+    pub out_anim: Option<PathBuf>,
+
+    /// import-ready for WolvenKit (default), or trimmed for reading
+    #[arg(value_enum, long, default_value_t = Fidelity::WolvenKit)]
+    pub fidelity: Fidelity,
+}
@@ -0,0 +1,104 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::widget::WidgetTree;
+use term_table::{
+    Table, TableStyle,
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
+
+use crate::{
+    args::Files,
+    read::{anim_path, read},
+};
+
+pub(crate) fn transplant(args: Args) {
+    let (source, source_anim) = read(&Files {
+        widget: args.from.clone(),
+        anim: args.from_anim.clone(),
+    });
+    let (mut widget, mut anim) = read(&Files {
+        widget: args.into.clone(),
+        anim: args.into_anim.clone(),
+    });
+    let subtree = args
+        .subtree
+        .resolve(&source.data.root_chunk)
+        .unwrap_or_else(|| panic!("couldn't find widget {} in source", args.subtree));
+    let parent = match &args.at {
+        Some(at) => at
+            .resolve(&widget.data.root_chunk)
+            .unwrap_or_else(|| panic!("couldn't find widget {at} in destination")),
+        None => vec![],
+    };
+    let copy = source
+        .data
+        .root_chunk
+        .widget_at(&subtree)
+        .expect("widget exists");
+    let (path, remap) = widget
+        .data
+        .root_chunk
+        .graft(&parent, args.index, copy)
+        .unwrap_or_else(|e| panic!("{e}"));
+    // siblings after the inserted widget got shifted
+    anim.data.root_chunk.remap_targets(&remap);
+    let copied = anim
+        .data
+        .root_chunk
+        .transplant(&source_anim.data.root_chunk, &subtree, &path)
+        .unwrap_or_else(|e| panic!("{e} in destination .inkanim"));
+
+    let mut table = Table::new();
+    table.style = TableStyle::rounded();
+    table.add_row(Row::new(vec![
+        TableCell::builder("sequence")
+            .alignment(Alignment::Center)
+            .build(),
+        TableCell::builder("definitions copied")
+            .alignment(Alignment::Center)
+            .build(),
+    ]));
+    for (sequence, count) in copied {
+        table.add_row(Row::new(vec![
+            TableCell::new(sequence),
+            TableCell::builder(count)
+                .alignment(Alignment::Right)
+                .build(),
+        ]));
+    }
+    let out_anim = args
+        .out_anim
+        .clone()
+        .unwrap_or_else(|| anim_path(&args.out));
+    if out_anim == args.out {
+        panic!("please specify .inkanim output path with --out-anim");
+    }
+    let json = args
+        .fidelity
+        .scope(|| serde_json::to_string_pretty(&widget))
+        .expect("serialize .inkwidget");
+    std::fs::write(&args.out, json).expect("write .inkwidget");
+    let json = args
+        .fidelity
+        .scope(|| serde_json::to_string_pretty(&anim))
+        .expect("serialize .inkanim");
+    std::fs::write(&out_anim, json).expect("write .inkanim");
+    println!("{}", table.render());
+    println!(
+        "{} transplanted at {}",
+        widget
+            .data
+            .root_chunk
+            .get_path_names(&path)
+            .unwrap_or_default()
+            .join("."),
+        path.iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(".")
+    );
+    println!("widget written to {}", args.out.display());
+    println!("anim written to {}", out_anim.display());
+}
@@ -1,3 +1,5 @@
+use clap::ValueEnum;
+
 use crate::args::Files;
 
 #[derive(clap::Args, Debug)]
@@ -5,4 +7,22 @@ use crate::args::Files;
 pub struct Args {
     #[command(flatten)]
     pub files: Files,
+
+    /// optionally output as Graphviz or Mermaid, instead of plain text (default)
+    #[arg(value_enum, long, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// optionally draw edges from sequences to the widgets they animate
+    ///
+    /// note: only applies to Graphviz and Mermaid formats
+    #[arg(long, default_value_t = false)]
+    pub sequences: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Text,
+    #[value(alias = "graphviz")]
+    Dot,
+    Mermaid,
 }
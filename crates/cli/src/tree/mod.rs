@@ -1,12 +1,28 @@
 mod args;
 pub(crate) use args::Args;
+use args::Format;
+
+use std::collections::BTreeSet;
 
 use inkanim_types::{
     File, InkWrapper,
-    widget::{InkChildren, Widget, inkWidgetLibraryResource},
+    anim::{InkAnimAnimationLibraryResource, Target},
+    widget::{Classname, InkChildren, Walk, Widget, WidgetNode, inkWidgetLibraryResource},
 };
 
-pub(crate) fn tree(_: Args, widget: File<inkWidgetLibraryResource>) {
+pub(crate) fn tree(
+    args: Args,
+    widget: File<inkWidgetLibraryResource>,
+    anim: InkAnimAnimationLibraryResource,
+) {
+    match args.format {
+        Format::Text => text(widget),
+        Format::Dot => println!("{}", dot(&widget.resource(), &anim, args.sequences)),
+        Format::Mermaid => println!("{}", mermaid(&widget.resource(), &anim, args.sequences)),
+    }
+}
+
+fn text(widget: File<inkWidgetLibraryResource>) {
     println!("---\n{}\n---", widget.header);
     let widget = widget.resource();
     for child in widget.root_chunk().root_widget.children() {
@@ -33,3 +49,113 @@ fn process(child: &InkWrapper<Widget>, mut indent: usize) {
         }
     }
 }
+
+/// node identifier, safe for both Graphviz and Mermaid
+fn id(indexes: &[usize]) -> String {
+    format!(
+        "w_{}",
+        indexes
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join("_")
+    )
+}
+
+fn parent_id(node: &WidgetNode) -> Option<String> {
+    (node.indexes.len() > 1).then(|| id(&node.indexes[..node.indexes.len() - 1]))
+}
+
+/// every distinct widget path animated by each sequence
+fn animated(anim: &InkAnimAnimationLibraryResource) -> Vec<(String, BTreeSet<Vec<usize>>)> {
+    anim.sequences
+        .iter()
+        .map(|sequence| {
+            let paths = sequence
+                .data
+                .targets
+                .iter()
+                .filter_map(|target| match target {
+                    Target::WithHandleId(handle) => Some(handle.data.path.clone()),
+                    Target::WithoutHandleId(_) => None,
+                })
+                .collect();
+            (sequence.name().to_string(), paths)
+        })
+        .collect()
+}
+
+fn dot(
+    widget: &inkWidgetLibraryResource,
+    anim: &InkAnimAnimationLibraryResource,
+    sequences: bool,
+) -> String {
+    let escape = |x: &str| x.replace('\\', "\\\\").replace('"', "\\\"");
+    let nodes = widget.walk();
+    let mut lines = vec![
+        "digraph inkwidget {".to_string(),
+        "  node [shape=box, fontname=\"monospace\"];".to_string(),
+    ];
+    for node in nodes.iter() {
+        lines.push(format!(
+            "  {} [label=\"{}\\n{}\\n{}\"];",
+            id(&node.indexes),
+            escape(node.names.last().map(|x| x.as_str()).unwrap_or_default()),
+            node.widget.data.classname(),
+            node.widget.handle_id,
+        ));
+    }
+    for node in nodes.iter() {
+        if let Some(parent) = parent_id(node) {
+            lines.push(format!("  {} -> {};", parent, id(&node.indexes)));
+        }
+    }
+    if sequences {
+        let known: BTreeSet<&[usize]> = nodes.iter().map(|x| x.indexes.as_slice()).collect();
+        for (idx, (name, paths)) in animated(anim).into_iter().enumerate() {
+            lines.push(format!(
+                "  s_{idx} [shape=ellipse, style=filled, label=\"{}\"];",
+                escape(&name)
+            ));
+            for path in paths.iter().filter(|x| known.contains(x.as_slice())) {
+                lines.push(format!("  s_{idx} -> {} [style=dashed];", id(path)));
+            }
+        }
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+fn mermaid(
+    widget: &inkWidgetLibraryResource,
+    anim: &InkAnimAnimationLibraryResource,
+    sequences: bool,
+) -> String {
+    let escape = |x: &str| x.replace('"', "#quot;");
+    let nodes = widget.walk();
+    let mut lines = vec!["flowchart TD".to_string()];
+    for node in nodes.iter() {
+        lines.push(format!(
+            "  {}[\"{}<br/>{}<br/>{}\"]",
+            id(&node.indexes),
+            escape(node.names.last().map(|x| x.as_str()).unwrap_or_default()),
+            node.widget.data.classname(),
+            node.widget.handle_id,
+        ));
+    }
+    for node in nodes.iter() {
+        if let Some(parent) = parent_id(node) {
+            lines.push(format!("  {} --> {}", parent, id(&node.indexes)));
+        }
+    }
+    if sequences {
+        let known: BTreeSet<&[usize]> = nodes.iter().map(|x| x.indexes.as_slice()).collect();
+        for (idx, (name, paths)) in animated(anim).into_iter().enumerate() {
+            lines.push(format!("  s_{idx}([\"{}\"])", escape(&name)));
+            for path in paths.iter().filter(|x| known.contains(x.as_slice())) {
+                lines.push(format!("  s_{idx} -.-> {}", id(path)));
+            }
+        }
+    }
+    lines.join("\n")
+}
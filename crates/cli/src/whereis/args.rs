@@ -1,4 +1,4 @@
-use crate::args::{Files, Mode, PathNames};
+use crate::args::{Files, Format, PathNames};
 
 #[derive(clap::Args, Debug)]
 #[command()]
@@ -6,9 +6,30 @@ pub struct Args {
     #[command(flatten)]
     pub files: Files,
 
+    /// unless searching by `--name`
     #[command(flatten)]
-    pub names: PathNames,
+    pub names: Option<PathNames>,
 
-    #[command(flatten)]
-    pub mode: Mode,
+    /// find widgets by name anywhere in the tree, instead of by path names
+    ///
+    /// e.g. "BOOTING_PROGRESS_Text", or as a glob "BOOTING_*_Text"
+    #[arg(
+        short,
+        long,
+        value_name = "NAME",
+        conflicts_with = "path",
+        required_unless_present = "path"
+    )]
+    pub name: Option<String>,
+
+    /// interpret `--name` as a regular expression instead
+    #[arg(long, requires = "name", conflicts_with = "path")]
+    pub regex: bool,
+
+    /// optionally output as JSON, instead of table (default)
+    ///
+    /// note: a single widget is output as its path names,
+    /// several ones (e.g. sharing a name) as their path names and indexes
+    #[arg(value_enum, long, default_value_t = Format::Table)]
+    pub output: Format,
 }
@@ -1,17 +1,35 @@
 mod args;
 pub(crate) use args::Args;
+use clap::{CommandFactory, error::ErrorKind};
 use inkanim_types::{
     anim::InkAnimAnimationLibraryResource,
-    widget::{WidgetTree, inkWidgetLibraryResource},
+    widget::{Classname, SearchableField, inkWidgetLibraryResource},
 };
+use regex::Regex;
+use serde_json::json;
 use term_table::{
     Table, TableStyle,
     row::Row,
     table_cell::{Alignment, TableCell},
 };
 
-fn json(names: &[&str], _: &[usize]) {
-    let json = serde_json::to_string_pretty(&names).unwrap();
+use crate::{
+    args::{Format, parse_regex},
+    cli::CLI,
+};
+
+/// path names of a single candidate, or names and indexes of each one otherwise
+fn json(candidates: &[(Vec<String>, Vec<usize>)]) {
+    let json = match candidates {
+        [(names, _)] => serde_json::to_string_pretty(names),
+        candidates => serde_json::to_string_pretty(
+            &candidates
+                .iter()
+                .map(|(names, indexes)| json!({ "names": names, "indexes": indexes }))
+                .collect::<Vec<_>>(),
+        ),
+    }
+    .unwrap();
     println!("{json}");
 }
 
@@ -43,35 +61,98 @@ fn table(names: &[&str], indexes: &[usize]) {
     println!("{}", table.render());
 }
 
+/// full match, where `*` stands for any characters and `?` for a single one
+fn glob(pattern: &str) -> Regex {
+    let pattern = regex::escape(pattern)
+        .replace(r"\*", ".*")
+        .replace(r"\?", ".");
+    Regex::new(&format!("^{pattern}$")).expect("escaped glob")
+}
+
+/// every widget whose own name matches, wherever it sits in the tree
+fn by_name(args: &Args, name: &str, widget: &inkWidgetLibraryResource) {
+    let pattern = if args.regex {
+        parse_regex(name).unwrap_or_else(|e| {
+            CLI::command()
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!("invalid --name pattern: {e}"),
+                )
+                .exit()
+        })
+    } else {
+        glob(name)
+    };
+    let found = widget
+        .search(|x| pattern.is_match(x))
+        .into_iter()
+        .filter(|x| x.field == SearchableField::Name)
+        .map(|hit| {
+            let names = widget
+                .unambiguous_path_names(&hit.node.indexes)
+                .unwrap_or(hit.node.names);
+            (hit.node.indexes, names, hit.node.widget.data.classname())
+        })
+        .collect::<Vec<_>>();
+    match args.output {
+        Format::Table => {
+            if found.is_empty() {
+                println!("couldn't find any widget named {name}");
+                return;
+            }
+            let mut table = Table::new();
+            table.style = TableStyle::rounded();
+            table.add_row(Row::new(
+                ["indexes", "names", "class"]
+                    .into_iter()
+                    .map(|x| TableCell::builder(x).alignment(Alignment::Center).build())
+                    .collect::<Vec<_>>(),
+            ));
+            for (indexes, names, classname) in found.iter() {
+                table.add_row(Row::new(vec![
+                    TableCell::new(
+                        indexes
+                            .iter()
+                            .map(|x| x.to_string())
+                            .collect::<Vec<_>>()
+                            .join("."),
+                    ),
+                    TableCell::new(names.join(".")),
+                    TableCell::new(classname),
+                ]));
+            }
+            println!("{}", table.render());
+        }
+        Format::Json => {
+            let found = found
+                .iter()
+                .map(|(indexes, names, classname)| {
+                    json!({ "indexes": indexes, "names": names, "class": classname })
+                })
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string_pretty(&found).unwrap());
+        }
+    }
+}
+
 pub(crate) fn whereis(
     args: Args,
     widget: inkWidgetLibraryResource,
     anim: InkAnimAnimationLibraryResource,
 ) {
+    if let Some(ref name) = args.name {
+        return by_name(&args, name, &widget);
+    }
     let names = args
         .names
-        .path
-        .iter()
-        .map(|x| x.as_str())
-        .collect::<Vec<_>>();
-    if names.is_empty() {
-        panic!("please specify widget path names");
-    }
+        .as_ref()
+        .expect("either --path or --name")
+        .resolve(&widget);
+    let names = names.iter().map(|x| x.as_str()).collect::<Vec<_>>();
     let depth = names.len();
     let sequences: Vec<&str> = anim.sequences.iter().map(|x| x.name()).collect();
-    let found = widget.get_path_indexes(&names);
-    if let Some(indexes) = found {
-        assert_eq!(depth, indexes.len());
-        match args.mode.output {
-            crate::args::Output::Table => {
-                table(names.as_slice(), indexes.as_slice());
-            }
-            crate::args::Output::Json => {
-                json(names.as_slice(), indexes.as_slice());
-            }
-            crate::args::Output::Reds => todo!(),
-        };
-    } else {
+    let found = widget.find_path_indexes(&names);
+    if found.is_empty() {
         println!(
             "couldn't find\n{}\nin sequence(s): {}",
             names
@@ -81,5 +162,33 @@ pub(crate) fn whereis(
                 .join(" . "),
             sequences.join(", "),
         );
+        return;
+    }
+    // list every candidate instead of guessing, when siblings share a name
+    let candidates = found
+        .into_iter()
+        .map(|indexes| {
+            assert_eq!(depth, indexes.len());
+            let names = widget
+                .unambiguous_path_names(&indexes)
+                .expect("found above");
+            (names, indexes)
+        })
+        .collect::<Vec<_>>();
+    if candidates.len() > 1 {
+        eprintln!(
+            "{} widgets share this path, disambiguate with e.g. {}",
+            candidates.len(),
+            candidates[1].0.join(".")
+        );
     }
+    match args.output {
+        Format::Table => {
+            for (names, indexes) in candidates.iter() {
+                let names = names.iter().map(|x| x.as_str()).collect::<Vec<_>>();
+                table(names.as_slice(), indexes.as_slice());
+            }
+        }
+        Format::Json => json(&candidates),
+    };
 }
@@ -0,0 +1,14 @@
+use std::path::PathBuf;
+
+use crate::args::Files;
+
+#[derive(clap::Args, Debug)]
+#[command()]
+pub struct Args {
+    #[command(flatten)]
+    pub files: Files,
+
+    /// .svg output path
+    #[arg(short, long, value_name = "FILE")]
+    pub out: PathBuf,
+}
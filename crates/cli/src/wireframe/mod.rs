@@ -0,0 +1,108 @@
+mod args;
+pub(crate) use args::Args;
+
+use inkanim_types::widget::{
+    Classname, WidgetNode,
+    geometry::{BoundingBox, Geometry},
+    inkWidgetLibraryResource,
+};
+
+/// font size for labels, in root widget pixels
+const FONT_SIZE: f32 = 14.;
+/// opacity for widgets hidden by themselves or by one of their ancestors
+const DIMMED: f32 = 0.25;
+
+pub(crate) fn colour(classname: &str) -> &'static str {
+    match classname {
+        "inkCanvasWidget" => "#4e79a7",
+        "inkHorizontalPanelWidget" => "#f28e2b",
+        "inkVerticalPanelWidget" => "#e15759",
+        "inkScrollAreaWidget" => "#76b7b2",
+        "inkUniformGridWidget" => "#59a14f",
+        "inkVirtualCompoundWidget" => "#edc948",
+        "inkFlexWidget" => "#b07aa1",
+        "inkCacheWidget" => "#ff9da7",
+        "inkTextWidget" => "#9c755f",
+        "inkImageWidget" => "#bab0ac",
+        "inkVideoWidget" => "#86bcb6",
+        "inkMaskWidget" => "#d37295",
+        "inkBorderWidget" => "#fabfd2",
+        "inkShapeWidget" => "#8cd17d",
+        "inkCircleWidget" => "#499894",
+        "inkRectangleWidget" => "#f1ce63",
+        "inkVectorGraphicWidget" => "#a0cbe8",
+        _ => "#ffffff",
+    }
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn shape(node: &WidgetNode, bounds: &BoundingBox, dimmed: bool) -> String {
+    let classname = node.widget.data.classname();
+    let colour = colour(&classname);
+    let indexes = node
+        .indexes
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+    let name = escape(node.names.last().map(|x| x.as_str()).unwrap_or_default());
+    format!(
+        r#"<g opacity="{opacity}"><title>{classname} {handle}
+{path}</title><rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{colour}" fill-opacity="0.05" stroke="{colour}"{dashes}/><text x="{text_x}" y="{text_y}" fill="{colour}">{name} ({indexes})</text></g>"#,
+        opacity = if dimmed { DIMMED } else { 1. },
+        handle = node.widget.handle_id,
+        path = escape(&node.names.join(" . ")),
+        x = bounds.x,
+        y = bounds.y,
+        width = bounds.width,
+        height = bounds.height,
+        dashes = if dimmed {
+            r#" stroke-dasharray="4 4""#
+        } else {
+            ""
+        },
+        text_x = bounds.x + 2.,
+        text_y = bounds.y + FONT_SIZE,
+    )
+}
+
+pub(crate) fn wireframe(args: Args, widget: inkWidgetLibraryResource) {
+    let root = &widget.root_chunk().root_widget.data;
+    let mut viewport = BoundingBox::new(0., 0., root.size.x, root.size.y);
+    let mut hidden: Vec<Vec<usize>> = vec![];
+    let mut shapes = vec![];
+    for (node, bounds) in widget.bounding_boxes() {
+        viewport = viewport.union(&bounds);
+        let visible = node
+            .widget
+            .data
+            .as_widget()
+            .map(|x| x.visible())
+            .unwrap_or(true);
+        if !visible {
+            hidden.push(node.indexes.clone());
+        }
+        let dimmed = hidden.iter().any(|x| node.indexes.starts_with(x));
+        shapes.push(shape(&node, &bounds, dimmed));
+    }
+    let svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{x} {y} {width} {height}" width="{width}" height="{height}" font-family="monospace" font-size="{FONT_SIZE}">
+<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="#1e1e1e"/>
+{shapes}
+</svg>
+"##,
+        x = viewport.x,
+        y = viewport.y,
+        width = viewport.width,
+        height = viewport.height,
+        shapes = shapes.join("\n"),
+    );
+    std::fs::write(&args.out, svg).expect("write .svg");
+    println!("wireframe written to {}", args.out.display());
+}
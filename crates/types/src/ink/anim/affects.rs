@@ -0,0 +1,67 @@
+//! Interpolators affecting a widget, directly or through its ancestors.
+//!
+//! Translations, scales or fades applied to a parent carry over to its children,
+//! so targeting the widget alone misses most of what moves it.
+
+use std::ops::Range;
+
+use serde::Serialize;
+
+use crate::HandleId;
+
+use super::{InkAnimSequence, Target};
+
+/// interpolator animating a widget or one of its ancestors
+#[derive(Debug, Clone, Serialize)]
+pub struct Influence {
+    /// definition index in the sequence
+    pub definition: usize,
+    /// interpolator index in the definition
+    pub interpolator: usize,
+    pub handle_id: HandleId,
+    /// animated widget path indexes, the widget itself or one of its ancestors
+    pub target: Vec<usize>,
+    /// animated property, e.g. "translation"
+    pub property: String,
+    /// animated time window, in seconds
+    pub window: Range<f32>,
+    /// whether it animates one of the ancestors rather than the widget itself
+    pub inherited: bool,
+}
+
+impl InkAnimSequence {
+    /// every interpolator whose target is the widget at `path` or one of its ancestors,
+    /// ordered by start time
+    pub fn influences(&self, path: &[usize]) -> Vec<Influence> {
+        let mut influences = self
+            .definitions
+            .iter()
+            .zip(self.targets.iter())
+            .enumerate()
+            .filter_map(|(idx, (definition, target))| match target {
+                Target::WithHandleId(handle) if path.starts_with(&handle.data.path) => {
+                    Some((idx, definition, &handle.data.path))
+                }
+                _ => None,
+            })
+            .flat_map(|(definition_idx, definition, target)| {
+                definition
+                    .data
+                    .interpolators
+                    .iter()
+                    .enumerate()
+                    .map(move |(idx, x)| Influence {
+                        definition: definition_idx,
+                        interpolator: idx,
+                        handle_id: x.handle_id,
+                        target: target.clone(),
+                        property: x.data.property(),
+                        window: x.data.starts()..x.data.ends(),
+                        inherited: target.len() < path.len(),
+                    })
+            })
+            .collect::<Vec<_>>();
+        influences.sort_by(|a, b| a.window.start.total_cmp(&b.window.start));
+        influences
+    }
+}
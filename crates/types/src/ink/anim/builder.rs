@@ -0,0 +1,400 @@
+//! Authoring new animations from scratch.
+//!
+//! e.g. `SequenceBuilder::new("intro").target(vec![1, 3]).fade_in(0., 0.3)`
+
+use std::marker::PhantomData;
+
+use crate::{Extras, File, HDRColor, HandleAllocator, InkWrapper, Name, Vector2};
+
+use super::{
+    BlankInkAnimSequenceTargetInfo, Direction, InkAnimAnimationLibraryResource, InkAnimDefinition,
+    InkAnimEvent, InkAnimInterpolator, InkAnimSequence, InkAnimSequenceTargetInfo, Interpolator,
+    MarkerEvent, Mode, PlaySoundEvent, Range, Target, Type,
+};
+
+/// handle ref ID of targets declaring events only
+const EVENTS_TARGET: i32 = -1;
+
+impl Interpolator {
+    /// linear interpolation from `start_value` to `end_value`
+    pub fn new(start_delay: f32, duration: f32, start_value: Range, end_value: Range) -> Self {
+        Self {
+            duration,
+            end_value,
+            interpolation_direction: Direction::To,
+            interpolation_mode: Mode::EasyIn,
+            interpolation_type: Type::Linear,
+            is_additive: false,
+            start_delay,
+            start_value,
+            use_relative_duration: false,
+        }
+    }
+    pub fn easing(mut self, r#type: Type, mode: Mode) -> Self {
+        self.interpolation_type = r#type;
+        self.interpolation_mode = mode;
+        self
+    }
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.interpolation_direction = direction;
+        self
+    }
+    pub fn additive(mut self) -> Self {
+        self.is_additive = true;
+        self
+    }
+}
+
+/// a definition alongside its target
+#[derive(Debug, Clone)]
+struct Track {
+    /// `None` for events
+    path: Option<Vec<usize>>,
+    interpolators: Vec<InkAnimInterpolator>,
+    events: Vec<InkAnimEvent>,
+}
+
+/// no definition opened yet
+#[derive(Debug, Clone)]
+pub struct Untargeted;
+/// a definition is opened, without any interpolator yet
+#[derive(Debug, Clone)]
+pub struct Targeted;
+/// the opened definition has at least one interpolator
+#[derive(Debug, Clone)]
+pub struct Animated;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Targeted {}
+    impl Sealed for super::Animated {}
+}
+
+/// states where interpolators and events can be added
+pub trait Opened: sealed::Sealed {}
+impl Opened for Targeted {}
+impl Opened for Animated {}
+
+/// fluent builder for [InkAnimSequence]
+///
+/// every [target](Self::target) opens a new definition,
+/// which subsequent interpolators get added to:
+/// state `S` makes sure one is opened beforehand.
+#[derive(Debug, Clone)]
+pub struct SequenceBuilder<S = Untargeted> {
+    name: String,
+    tracks: Vec<Track>,
+    state: PhantomData<S>,
+}
+
+impl SequenceBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            tracks: vec![],
+            state: PhantomData,
+        }
+    }
+}
+
+impl<S> SequenceBuilder<S> {
+    fn into_state<T>(self) -> SequenceBuilder<T> {
+        SequenceBuilder {
+            name: self.name,
+            tracks: self.tracks,
+            state: PhantomData,
+        }
+    }
+
+    fn open(mut self, path: Option<Vec<usize>>) -> SequenceBuilder<Targeted> {
+        self.tracks.push(Track {
+            path,
+            interpolators: vec![],
+            events: vec![],
+        });
+        self.into_state()
+    }
+
+    /// animate widget at path indexes (relative to root widget)
+    pub fn target(self, path: Vec<usize>) -> SequenceBuilder<Targeted> {
+        self.open(Some(path))
+    }
+
+    /// declare events, which are not related to any widget
+    pub fn events(self) -> SequenceBuilder<Targeted> {
+        self.open(None)
+    }
+
+    /// build sequence with handle IDs from `handles`
+    ///
+    /// e.g. to add it to an existing resource, see [HandleAllocator::over]
+    pub fn build_with(self, handles: &mut HandleAllocator) -> InkWrapper<InkAnimSequence> {
+        let handle_id = handles.allocate();
+        let mut definitions = Vec::with_capacity(self.tracks.len());
+        let mut paths = Vec::with_capacity(self.tracks.len());
+        for track in self.tracks {
+            let handle_id = handles.allocate();
+            let events = track.events.into_iter().map(|x| handles.wrap(x)).collect();
+            let interpolators = track
+                .interpolators
+                .into_iter()
+                .map(|x| handles.wrap(x))
+                .collect();
+            definitions.push(InkWrapper {
+                handle_id,
+                data: InkAnimDefinition {
+                    events,
+                    interpolators,
+                },
+            });
+            paths.push(track.path);
+        }
+        let targets = paths
+            .into_iter()
+            .map(|path| match path {
+                Some(path) => {
+                    Target::WithHandleId(handles.wrap(InkAnimSequenceTargetInfo { path }))
+                }
+                None => Target::WithoutHandleId(BlankInkAnimSequenceTargetInfo {
+                    handle_ref_id: EVENTS_TARGET,
+                }),
+            })
+            .collect();
+        InkWrapper {
+            handle_id,
+            data: InkAnimSequence {
+                definitions,
+                name: Name::from(self.name.as_str()),
+                targets,
+            },
+        }
+    }
+
+    /// build sequence on its own, with handle IDs starting from `0`
+    pub fn build(self) -> InkWrapper<InkAnimSequence> {
+        self.build_with(&mut HandleAllocator::default())
+    }
+
+    /// build a whole .inkanim containing only this sequence
+    pub fn file(self, archive_file_name: &str) -> File<InkAnimAnimationLibraryResource> {
+        LibraryBuilder::new().sequence(self).file(archive_file_name)
+    }
+}
+
+impl<S: Opened> SequenceBuilder<S> {
+    fn current(&mut self) -> &mut Track {
+        self.tracks.last_mut().expect("opened by target or events")
+    }
+
+    /// add any interpolator to current target
+    pub fn interpolator(mut self, interpolator: InkAnimInterpolator) -> SequenceBuilder<Animated> {
+        self.current().interpolators.push(interpolator);
+        self.into_state()
+    }
+
+    /// add any event to current target
+    pub fn event(mut self, event: InkAnimEvent) -> Self {
+        self.current().events.push(event);
+        self
+    }
+
+    pub fn fade_in(self, starts: f32, duration: f32) -> SequenceBuilder<Animated> {
+        self.transparency(starts, duration, 0., 1.)
+    }
+
+    pub fn fade_out(self, starts: f32, duration: f32) -> SequenceBuilder<Animated> {
+        self.transparency(starts, duration, 1., 0.)
+    }
+
+    pub fn transparency(
+        self,
+        starts: f32,
+        duration: f32,
+        from: f32,
+        to: f32,
+    ) -> SequenceBuilder<Animated> {
+        self.interpolator(InkAnimInterpolator::inkanimTransparencyInterpolator(
+            Interpolator::new(starts, duration, Range::Percent(from), Range::Percent(to)),
+        ))
+    }
+
+    pub fn translate(
+        self,
+        starts: f32,
+        duration: f32,
+        from: Vector2,
+        to: Vector2,
+    ) -> SequenceBuilder<Animated> {
+        self.interpolator(InkAnimInterpolator::inkanimTranslationInterpolator(
+            Interpolator::new(starts, duration, Range::Position(from), Range::Position(to)),
+        ))
+    }
+
+    pub fn scale(
+        self,
+        starts: f32,
+        duration: f32,
+        from: Vector2,
+        to: Vector2,
+    ) -> SequenceBuilder<Animated> {
+        self.interpolator(InkAnimInterpolator::inkanimScaleInterpolator(
+            Interpolator::new(starts, duration, Range::Position(from), Range::Position(to)),
+        ))
+    }
+
+    pub fn size(
+        self,
+        starts: f32,
+        duration: f32,
+        from: Vector2,
+        to: Vector2,
+    ) -> SequenceBuilder<Animated> {
+        self.interpolator(InkAnimInterpolator::inkanimSizeInterpolator(
+            Interpolator::new(starts, duration, Range::Position(from), Range::Position(to)),
+        ))
+    }
+
+    /// rotation, in degrees
+    pub fn rotate(
+        self,
+        starts: f32,
+        duration: f32,
+        from: f32,
+        to: f32,
+    ) -> SequenceBuilder<Animated> {
+        self.interpolator(InkAnimInterpolator::inkanimRotationInterpolator(
+            Interpolator::new(starts, duration, Range::Percent(from), Range::Percent(to)),
+        ))
+    }
+
+    pub fn color(
+        self,
+        starts: f32,
+        duration: f32,
+        from: HDRColor,
+        to: HDRColor,
+    ) -> SequenceBuilder<Animated> {
+        self.interpolator(InkAnimInterpolator::inkanimColorInterpolator(
+            Interpolator::new(starts, duration, Range::Color(from), Range::Color(to)),
+        ))
+    }
+
+    pub fn play_sound(self, starts: f32, sound_event_name: &str) -> Self {
+        self.event(InkAnimEvent::inkanimPlaySoundEvent(PlaySoundEvent {
+            sound_event_name: Name::from(sound_event_name),
+            start_time: starts,
+        }))
+    }
+
+    pub fn marker(self, starts: f32, marker_name: &str) -> Self {
+        self.event(InkAnimEvent::inkanimMarkerEvent(MarkerEvent {
+            marker_name: Name::from(marker_name),
+            start_time: starts,
+        }))
+    }
+}
+
+impl SequenceBuilder<Animated> {
+    /// change easing of the last added interpolator
+    pub fn easing(mut self, r#type: Type, mode: Mode) -> Self {
+        let interpolator = self
+            .current()
+            .interpolators
+            .last_mut()
+            .expect("added by interpolator")
+            .as_mut();
+        interpolator.interpolation_type = r#type;
+        interpolator.interpolation_mode = mode;
+        self
+    }
+}
+
+/// fluent builder for [InkAnimAnimationLibraryResource]
+#[derive(Debug, Clone, Default)]
+pub struct LibraryBuilder {
+    sequences: Vec<SequenceBuilder>,
+}
+
+impl LibraryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sequence<S>(mut self, sequence: SequenceBuilder<S>) -> Self {
+        self.sequences.push(sequence.into_state());
+        self
+    }
+
+    /// build resource, with handle IDs unique across all sequences
+    pub fn build(self) -> InkAnimAnimationLibraryResource {
+        let mut handles = HandleAllocator::default();
+        InkAnimAnimationLibraryResource {
+            sequences: self
+                .sequences
+                .into_iter()
+                .map(|x| x.build_with(&mut handles))
+                .collect(),
+            extras: Extras::default(),
+        }
+    }
+
+    /// build a whole .inkanim
+    ///
+    /// e.g. `base\gameplay\gui\intro_animations.inkanim`
+    pub fn file(self, archive_file_name: &str) -> File<InkAnimAnimationLibraryResource> {
+        File::new(archive_file_name, self.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fidelity;
+
+    /// built library survives a round-trip through WolvenKit JSON
+    #[test]
+    fn library_round_trip() {
+        let file = LibraryBuilder::new()
+            .sequence(
+                SequenceBuilder::new("intro")
+                    .target(vec![1, 3])
+                    .fade_in(0., 0.3)
+                    .translate(
+                        0.1,
+                        0.5,
+                        Vector2 { x: 0., y: 40. },
+                        Vector2 { x: 0., y: 0. },
+                    )
+                    .easing(Type::Quadratic, Mode::EasyOut)
+                    .events()
+                    .play_sound(0., "ui_menu_onpress")
+                    .marker(0.5, "intro_done"),
+            )
+            .sequence(
+                SequenceBuilder::new("outro")
+                    .target(vec![0])
+                    .fade_out(0., 0.2),
+            )
+            .file("base\\gameplay\\gui\\test.inkanim");
+        let json = Fidelity::WolvenKit
+            .scope(|| serde_json::to_string(&file))
+            .unwrap();
+        let library = serde_json::from_str::<File<InkAnimAnimationLibraryResource>>(&json)
+            .unwrap()
+            .resource();
+        assert_eq!(library.sequences.len(), 2);
+        let intro = &library.sequences[0].data;
+        assert_eq!(intro.name.as_str(), "intro");
+        assert_eq!(intro.definitions.len(), intro.targets.len());
+        assert!(matches!(&intro.targets[0], Target::WithHandleId(x) if x.data.path == vec![1, 3]));
+        assert!(matches!(
+            &intro.targets[1],
+            Target::WithoutHandleId(BlankInkAnimSequenceTargetInfo {
+                handle_ref_id: EVENTS_TARGET
+            })
+        ));
+        assert_eq!(intro.definitions[0].data.interpolators.len(), 2);
+        assert_eq!(intro.definitions[1].data.events.len(), 2);
+        assert_eq!(library.sequences[1].data.name.as_str(), "outro");
+    }
+}
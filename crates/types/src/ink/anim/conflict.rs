@@ -0,0 +1,112 @@
+//! Interpolators fighting over the same property of the same widget.
+//!
+//! Unless additive, overlapping interpolators each set the property in turn,
+//! which shows up as jitter in-game.
+
+use std::ops::Range;
+
+use serde::Serialize;
+
+use crate::HandleId;
+
+use super::{InkAnimInterpolator, InkAnimInterpolatorType, InkAnimSequence, Target};
+
+/// overlaps shorter than this, in seconds, are float rounding on back-to-back interpolators
+const TOLERANCE: f32 = 0.001;
+
+/// two interpolators animating the same property of the same widget at the same time
+#[derive(Debug, Clone, Serialize)]
+pub struct Conflict {
+    /// animated widget path indexes
+    pub path: Vec<usize>,
+    #[serde(skip)]
+    pub kind: InkAnimInterpolatorType,
+    /// animated property, e.g. "transparency" or "effect (linear wipe) LinearWipe_0.transition"
+    pub property: String,
+    /// handle IDs of both interpolators, in document order
+    pub interpolators: [HandleId; 2],
+    /// overlapping time window, in seconds
+    pub window: Range<f32>,
+    /// whether at least one of them is additive, blending instead of fighting
+    pub additive: bool,
+}
+
+impl Conflict {
+    /// both interpolators overwrite the property
+    pub fn is_fight(&self) -> bool {
+        !self.additive
+    }
+}
+
+/// an interpolator alongside what it animates
+struct Animated<'a> {
+    path: &'a [usize],
+    kind: InkAnimInterpolatorType,
+    /// effect and param names, for effect interpolators
+    effect: Option<(&'a str, &'a str)>,
+    handle_id: HandleId,
+    interpolator: &'a InkAnimInterpolator,
+}
+
+impl Animated<'_> {
+    fn same_property(&self, other: &Self) -> bool {
+        self.path == other.path && self.kind == other.kind && self.effect == other.effect
+    }
+}
+
+impl InkAnimSequence {
+    /// every pair of interpolators animating the same property of the same widget
+    /// within overlapping `[starts, ends)` windows,
+    /// ignoring overlaps under a millisecond
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let animated = self
+            .definitions
+            .iter()
+            .zip(self.targets.iter())
+            .filter_map(|(definition, target)| match target {
+                Target::WithHandleId(handle) => Some((definition, &handle.data.path)),
+                Target::WithoutHandleId(_) => None,
+            })
+            .flat_map(|(definition, path)| {
+                definition.data.interpolators.iter().map(move |x| Animated {
+                    path,
+                    kind: x.data.kind(),
+                    effect: match &x.data {
+                        InkAnimInterpolator::inkanimEffectInterpolator(effect) => {
+                            Some((effect.effect_name.as_str(), effect.param_name.as_str()))
+                        }
+                        _ => None,
+                    },
+                    handle_id: x.handle_id,
+                    interpolator: &x.data,
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut conflicts = vec![];
+        for (idx, first) in animated.iter().enumerate() {
+            for second in animated[idx + 1..].iter() {
+                if !first.same_property(second) {
+                    continue;
+                }
+                let starts = first
+                    .interpolator
+                    .starts()
+                    .max(second.interpolator.starts());
+                let ends = first.interpolator.ends().min(second.interpolator.ends());
+                if ends - starts < TOLERANCE {
+                    continue;
+                }
+                conflicts.push(Conflict {
+                    path: first.path.to_vec(),
+                    kind: first.kind,
+                    property: first.interpolator.property(),
+                    interpolators: [first.handle_id, second.handle_id],
+                    window: starts..ends,
+                    additive: first.interpolator.as_ref().is_additive
+                        || second.interpolator.as_ref().is_additive,
+                });
+            }
+        }
+        conflicts
+    }
+}
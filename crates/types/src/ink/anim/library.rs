@@ -0,0 +1,87 @@
+//! Moving sequences between animation libraries.
+//!
+//! Handle IDs are unique across a whole .inkanim, so they get reassigned along the way.
+
+use crate::HandleAllocator;
+
+use super::InkAnimAnimationLibraryResource;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SequenceError {
+    /// no sequence with this name
+    NotFound(String),
+    /// another sequence already has this name
+    NameTaken(String),
+}
+
+impl std::fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(name) => write!(f, "no sequence named {name}"),
+            Self::NameTaken(name) => write!(f, "sequence {name} already exists"),
+        }
+    }
+}
+
+impl std::error::Error for SequenceError {}
+
+impl InkAnimAnimationLibraryResource {
+    /// new library containing only the sequence named `name`, with handle IDs starting from `0`
+    pub fn extract(&self, name: &str) -> Option<Self> {
+        let sequence = self.sequences.iter().find(|x| x.name() == name)?;
+        Some(HandleAllocator::default().adopt(Self {
+            sequences: vec![sequence.clone()],
+            extras: self.extras.clone(),
+        }))
+    }
+
+    /// one new library per sequence, in order
+    pub fn split(&self) -> Vec<Self> {
+        self.sequences
+            .iter()
+            .map(|sequence| {
+                HandleAllocator::default().adopt(Self {
+                    sequences: vec![sequence.clone()],
+                    extras: self.extras.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// append every sequence from `other`, with fresh handle IDs
+    ///
+    /// sequences whose name is already taken get suffixed with `_2`, `_3`, etc:
+    /// returns these `(previous, new)` names.
+    pub fn merge(&mut self, other: Self) -> Vec<(String, String)> {
+        let mut handles = HandleAllocator::over(self);
+        let mut renamed = vec![];
+        for sequence in other.sequences {
+            let mut sequence = handles.adopt(sequence);
+            let name = sequence.name().to_string();
+            if self.sequences.iter().any(|x| x.name() == name) {
+                let unique = (2..)
+                    .map(|n| format!("{name}_{n}"))
+                    .find(|x| !self.sequences.iter().any(|y| y.name() == x))
+                    .expect("unused name");
+                sequence.data.name = unique.as_str().into();
+                renamed.push((name, unique));
+            }
+            self.sequences.push(sequence);
+        }
+        renamed
+    }
+
+    /// rename sequence `from` to `to`, as passed to `PlayLibraryAnimation`
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<(), SequenceError> {
+        if from != to && self.sequences.iter().any(|x| x.name() == to) {
+            return Err(SequenceError::NameTaken(to.to_string()));
+        }
+        let sequence = self
+            .sequences
+            .iter_mut()
+            .find(|x| x.name() == from)
+            .ok_or_else(|| SequenceError::NotFound(from.to_string()))?;
+        sequence.data.name = to.into();
+        Ok(())
+    }
+}
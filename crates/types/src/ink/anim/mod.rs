@@ -1,12 +1,25 @@
 //! All animations in Cyberpunk 2077 UI
 //! are similar to web and traditional 2D animations frameworks.
 
+mod affects;
+mod builder;
+mod conflict;
 mod display;
+mod library;
+mod retime;
+
+pub use affects::*;
+pub use builder::*;
+pub use conflict::*;
+pub use library::*;
 
 use serde::{Deserialize, Serialize};
 use serde_aux::prelude::*;
 
-use crate::{HDRColor, Name, Vector2};
+use crate::{
+    Extras, Fidelity, HDRColor, Name, Vector2, serialize_bool,
+    widget::{PathRemap, SiblingOrNested},
+};
 
 use super::InkWrapper;
 
@@ -115,12 +128,18 @@ pub struct Interpolator {
     pub interpolation_direction: Direction,
     pub interpolation_mode: Mode,
     pub interpolation_type: Type,
-    #[serde(deserialize_with = "deserialize_bool_from_anything")]
+    #[serde(
+        deserialize_with = "deserialize_bool_from_anything",
+        serialize_with = "serialize_bool"
+    )]
     pub is_additive: bool,
     pub start_delay: f32,
     #[serde(deserialize_with = "deserialize_vector2_from_anything")]
     pub start_value: Range,
-    #[serde(deserialize_with = "deserialize_bool_from_anything")]
+    #[serde(
+        deserialize_with = "deserialize_bool_from_anything",
+        serialize_with = "serialize_bool"
+    )]
     pub use_relative_duration: bool,
 }
 
@@ -199,6 +218,28 @@ impl AsRef<Interpolator> for InkAnimInterpolator {
     }
 }
 
+impl AsMut<Interpolator> for InkAnimInterpolator {
+    fn as_mut(&mut self) -> &mut Interpolator {
+        match self {
+            Self::inkanimEffectInterpolator(interpolator) => &mut interpolator.base,
+            Self::inkanimScaleInterpolator(interpolator)
+            | Self::inkanimTranslationInterpolator(interpolator)
+            | Self::inkanimTransparencyInterpolator(interpolator)
+            | Self::inkanimSizeInterpolator(interpolator)
+            | Self::inkanimColorInterpolator(interpolator)
+            | Self::inkanimTextValueProgressInterpolator(interpolator)
+            | Self::inkanimAnchorInterpolator(interpolator)
+            | Self::inkanimPivotInterpolator(interpolator)
+            | Self::inkanimShearInterpolator(interpolator)
+            | Self::inkanimRotationInterpolator(interpolator)
+            | Self::inkanimMarginInterpolator(interpolator)
+            | Self::inkanimPaddingInterpolator(interpolator)
+            | Self::inkanimTextReplaceInterpolator(interpolator)
+            | Self::inkanimTextOffsetInterpolator(interpolator) => interpolator,
+        }
+    }
+}
+
 impl InkAnimInterpolator {
     pub fn as_short_display(&self) -> &str {
         match self {
@@ -233,6 +274,40 @@ impl InkAnimInterpolator {
             Self::inkanimTextOffsetInterpolator(_) => "text offset",
         }
     }
+    /// kind of interpolation, regardless of fade direction
+    pub fn kind(&self) -> InkAnimInterpolatorType {
+        match self {
+            Self::inkanimScaleInterpolator(_) => InkAnimInterpolatorType::Scale,
+            Self::inkanimTranslationInterpolator(_) => InkAnimInterpolatorType::Translation,
+            Self::inkanimTransparencyInterpolator(_) => InkAnimInterpolatorType::Transparency(None),
+            Self::inkanimSizeInterpolator(_) => InkAnimInterpolatorType::Size,
+            Self::inkanimColorInterpolator(_) => InkAnimInterpolatorType::Color,
+            Self::inkanimTextValueProgressInterpolator(_) => {
+                InkAnimInterpolatorType::TextValueProgress
+            }
+            Self::inkanimEffectInterpolator(_) => InkAnimInterpolatorType::Effect,
+            Self::inkanimAnchorInterpolator(_) => InkAnimInterpolatorType::Anchor,
+            Self::inkanimPivotInterpolator(_) => InkAnimInterpolatorType::Pivot,
+            Self::inkanimShearInterpolator(_) => InkAnimInterpolatorType::Shear,
+            Self::inkanimRotationInterpolator(_) => InkAnimInterpolatorType::Rotation,
+            Self::inkanimMarginInterpolator(_) => InkAnimInterpolatorType::Margin,
+            Self::inkanimPaddingInterpolator(_) => InkAnimInterpolatorType::Padding,
+            Self::inkanimTextReplaceInterpolator(_) => InkAnimInterpolatorType::TextReplace,
+            Self::inkanimTextOffsetInterpolator(_) => InkAnimInterpolatorType::TextOffset,
+        }
+    }
+    /// animated property, e.g. "transparency" or "effect (linear wipe) LinearWipe_0.transition"
+    pub fn property(&self) -> String {
+        match self {
+            Self::inkanimEffectInterpolator(effect) => format!(
+                "{} {}.{}",
+                self.as_short_display(),
+                effect.effect_name.as_str(),
+                effect.param_name.as_str()
+            ),
+            _ => self.as_short_display().to_string(),
+        }
+    }
     pub fn starts(&self) -> f32 {
         self.as_ref().start_delay
     }
@@ -296,16 +371,77 @@ impl PartialEq<InkAnimInterpolatorType> for InkAnimInterpolator {
     }
 }
 
-/// a sequence of interpolators
+/// see [NativeDB](https://nativedb.red4ext.com/inkanimPlaySoundEvent)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaySoundEvent {
+    pub sound_event_name: Name,
+    pub start_time: f32,
+}
+
+/// see [NativeDB](https://nativedb.red4ext.com/inkanimChangeStateEvent)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeStateEvent {
+    pub state: Name,
+    pub start_time: f32,
+}
+
+/// see [NativeDB](https://nativedb.red4ext.com/inkanimMarkerEvent)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct MarkerEvent {
+    pub marker_name: Name,
+    pub start_time: f32,
+}
+
+/// any event, triggered at a given time
+///
+/// see [NativeDB](https://nativedb.red4ext.com/inkanimEvent)
+#[allow(clippy::enum_variant_names)]
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "$type")]
+pub enum InkAnimEvent {
+    inkanimPlaySoundEvent(PlaySoundEvent),
+    inkanimChangeStateEvent(ChangeStateEvent),
+    inkanimMarkerEvent(MarkerEvent),
+    /// any other kind of event, without its content
+    #[serde(other)]
+    Unknown,
+}
+
+impl InkAnimEvent {
+    pub fn starts(&self) -> Option<f32> {
+        match self {
+            Self::inkanimPlaySoundEvent(event) => Some(event.start_time),
+            Self::inkanimChangeStateEvent(event) => Some(event.start_time),
+            Self::inkanimMarkerEvent(event) => Some(event.start_time),
+            Self::Unknown => None,
+        }
+    }
+    pub(crate) fn start_time_mut(&mut self) -> Option<&mut f32> {
+        match self {
+            Self::inkanimPlaySoundEvent(event) => Some(&mut event.start_time),
+            Self::inkanimChangeStateEvent(event) => Some(&mut event.start_time),
+            Self::inkanimMarkerEvent(event) => Some(&mut event.start_time),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// a sequence of interpolators and events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "$type", rename = "inkanimDefinition", rename_all = "camelCase")]
 pub struct InkAnimDefinition {
+    #[serde(default)]
+    pub events: Vec<InkWrapper<InkAnimEvent>>,
     pub interpolators: Vec<InkWrapper<InkAnimInterpolator>>,
 }
 
 /// a sequence of interpolations (interpolators and events)
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(tag = "$type", rename = "inkanimSequence", rename_all = "camelCase")]
 pub struct InkAnimSequence {
     /// describe the interpolations played
     ///
@@ -319,9 +455,15 @@ pub struct InkAnimSequence {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(
+    tag = "$type",
+    rename = "inkanimAnimationLibraryResource",
+    rename_all = "camelCase"
+)]
 pub struct InkAnimAnimationLibraryResource {
     pub sequences: Vec<InkWrapper<InkAnimSequence>>,
+    #[serde(default, flatten)]
+    pub extras: Extras,
 }
 
 /// when related to interpolator(s),
@@ -330,7 +472,11 @@ pub struct InkAnimAnimationLibraryResource {
 /// see [NativeDB](https://nativedb.red4ext.com/inkanimSequenceTargetInfo)
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(
+    tag = "$type",
+    rename = "inkanimSequenceTargetInfo",
+    rename_all = "camelCase"
+)]
 pub struct InkAnimSequenceTargetInfo {
     /// path to the nested element (indexes)
     ///
@@ -343,10 +489,25 @@ pub struct InkAnimSequenceTargetInfo {
 #[serde(rename_all = "PascalCase")]
 pub struct BlankInkAnimSequenceTargetInfo {
     /// typically here the value is `-1`
-    #[serde(deserialize_with = "deserialize_number_from_string")]
+    #[serde(
+        deserialize_with = "deserialize_number_from_string",
+        serialize_with = "serialize_handle_ref_id"
+    )]
     pub handle_ref_id: i32,
 }
 
+/// WolvenKit stores handle ref IDs as strings
+fn serialize_handle_ref_id<S>(value: &i32, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if Fidelity::trimmed() {
+        serializer.serialize_i32(*value)
+    } else {
+        serializer.collect_str(value)
+    }
+}
+
 /// any target
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -380,3 +541,94 @@ impl InkWrapper<InkAnimSequence> {
         self.data.name.as_str()
     }
 }
+
+impl InkAnimAnimationLibraryResource {
+    /// rewrite every target path after the widget tree got edited
+    ///
+    /// definitions targeting a removed widget are dropped alongside their target,
+    /// since their path would otherwise point at whichever sibling shifted into place:
+    /// returns their `(sequence, definition)` indexes, as before the edit.
+    pub fn remap_targets(&mut self, remap: &PathRemap) -> Vec<(usize, usize)> {
+        let mut removed = vec![];
+        for (idx_sequence, sequence) in self.sequences.iter_mut().enumerate() {
+            let mut orphans = vec![];
+            for (idx_target, target) in sequence.data.targets.iter_mut().enumerate() {
+                if let Target::WithHandleId(handle) = target {
+                    match remap.apply(&handle.data.path) {
+                        Some(path) => handle.data.path = path,
+                        None => orphans.push(idx_target),
+                    }
+                }
+            }
+            for idx in orphans.iter().rev() {
+                sequence.data.definitions.remove(*idx);
+                sequence.data.targets.remove(*idx);
+            }
+            removed.extend(orphans.into_iter().map(|x| (idx_sequence, x)));
+        }
+        removed
+    }
+
+    /// point every target at or nested below `from` to `to` instead
+    ///
+    /// returns the number of definitions retargeted, for each sequence.
+    pub fn retarget(&mut self, from: &[usize], to: &[usize]) -> Vec<usize> {
+        self.sequences
+            .iter_mut()
+            .map(|sequence| {
+                let mut changed = 0;
+                for target in sequence.data.targets.iter_mut() {
+                    if let Target::WithHandleId(handle) = target
+                        && handle.data.path.sibling_or_nested(from)
+                    {
+                        handle.data.path = to
+                            .iter()
+                            .chain(handle.data.path[from.len()..].iter())
+                            .cloned()
+                            .collect();
+                        changed += 1;
+                    }
+                }
+                changed
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{File, widget::inkWidgetLibraryResource};
+
+    fn count_at(anim: &InkAnimAnimationLibraryResource, path: &[usize]) -> usize {
+        anim.sequences
+            .iter()
+            .flat_map(|x| x.data.targets.iter())
+            .filter(|x| matches!(x, Target::WithHandleId(x) if x.data.path == path))
+            .count()
+    }
+
+    #[test]
+    fn remap_targets_drops_removed_widget_definitions() {
+        let mut widget = serde_json::from_str::<File<inkWidgetLibraryResource>>(include_str!(
+            "../../../../../inkwidget_biomoncheck.json"
+        ))
+        .unwrap()
+        .resource();
+        let mut anim = serde_json::from_str::<File<InkAnimAnimationLibraryResource>>(include_str!(
+            "../../../../../inkanim_biomoncheck.json"
+        ))
+        .unwrap()
+        .resource();
+        let removed = count_at(&anim, &[0, 0, 1]);
+        let next = count_at(&anim, &[0, 0, 2]);
+        assert!(removed > 0);
+        let (_, remap) = widget.remove(&[0, 0, 1]).unwrap();
+        let dropped = anim.remap_targets(&remap);
+        assert_eq!(dropped.len(), removed);
+        assert_eq!(count_at(&anim, &[0, 0, 1]), next);
+        for sequence in anim.sequences.iter() {
+            assert_eq!(sequence.data.definitions.len(), sequence.data.targets.len());
+        }
+    }
+}
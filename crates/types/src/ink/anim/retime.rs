@@ -0,0 +1,99 @@
+//! Retiming a whole sequence at once,
+//! instead of editing every `startDelay` and `duration` by hand.
+
+use std::ops::RangeBounds;
+
+use super::{InkAnimSequence, Interpolator};
+
+impl InkAnimSequence {
+    fn interpolators_mut(&mut self) -> impl Iterator<Item = &mut Interpolator> {
+        self.definitions
+            .iter_mut()
+            .flat_map(|x| x.data.interpolators.iter_mut())
+            .map(|x| x.data.as_mut())
+    }
+
+    fn events_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+        self.definitions
+            .iter_mut()
+            .flat_map(|x| x.data.events.iter_mut())
+            .filter_map(|x| x.data.start_time_mut())
+    }
+
+    /// total duration in seconds: last interpolator end or event, whichever comes last
+    pub fn duration(&self) -> f32 {
+        let definitions = self.definitions.iter().map(|x| &x.data);
+        let interpolators = definitions
+            .clone()
+            .flat_map(|x| x.interpolators.iter())
+            .map(|x| x.data.ends());
+        let events = definitions
+            .flat_map(|x| x.events.iter())
+            .filter_map(|x| x.data.starts());
+        interpolators.chain(events).fold(0., f32::max)
+    }
+
+    /// multiply every start and duration by `factor`
+    ///
+    /// e.g. `0.8` plays the whole sequence 20% faster.
+    pub fn scale(&mut self, factor: f32) {
+        for interpolator in self.interpolators_mut() {
+            interpolator.start_delay *= factor;
+            interpolator.duration *= factor;
+        }
+        for start in self.events_mut() {
+            *start *= factor;
+        }
+    }
+
+    /// scale the sequence so that it lasts exactly `duration` seconds
+    pub fn stretch(&mut self, duration: f32) {
+        let current = self.duration();
+        if current > 0. {
+            self.scale(duration / current);
+        }
+    }
+
+    /// delay by `offset` seconds everything starting within `window`
+    ///
+    /// a negative `offset` brings them forward instead, but never before `0.`.
+    pub fn shift(&mut self, window: impl RangeBounds<f32>, offset: f32) {
+        for interpolator in self.interpolators_mut() {
+            if window.contains(&interpolator.start_delay) {
+                interpolator.start_delay = (interpolator.start_delay + offset).max(0.);
+            }
+        }
+        for start in self.events_mut() {
+            if window.contains(start) {
+                *start = (*start + offset).max(0.);
+            }
+        }
+    }
+
+    /// cut everything past `duration` seconds
+    ///
+    /// interpolators starting later are kept, but last `0.` seconds.
+    pub fn clamp(&mut self, duration: f32) {
+        for interpolator in self.interpolators_mut() {
+            let ends = (interpolator.start_delay + interpolator.duration).min(duration);
+            interpolator.start_delay = interpolator.start_delay.min(duration);
+            interpolator.duration = ends - interpolator.start_delay;
+        }
+        for start in self.events_mut() {
+            *start = start.min(duration);
+        }
+    }
+
+    /// round every start and end to the nearest frame, at `fps` frames per second
+    pub fn snap(&mut self, fps: f32) {
+        let snap = |x: f32| (x * fps).round() / fps;
+        for interpolator in self.interpolators_mut() {
+            let ends = snap(interpolator.start_delay + interpolator.duration);
+            interpolator.start_delay = snap(interpolator.start_delay);
+            interpolator.duration = (ends - interpolator.start_delay).max(0.);
+        }
+        for start in self.events_mut() {
+            *start = snap(*start);
+        }
+    }
+}
@@ -94,11 +94,12 @@ where
         where
             A: MapAccess<'de>,
         {
-            while let Some(key) = map.next_key::<&str>()? {
+            while let Some(key) = map.next_key::<String>()? {
                 if key == "value" {
-                    let value: &str = map.next_value()?;
-                    return self.visit_str(value);
+                    let value: String = map.next_value()?;
+                    return self.visit_str(&value);
                 }
+                map.next_value::<de::IgnoredAny>()?;
             }
             Err(de::Error::custom("invalid map sequence"))
         }
@@ -209,14 +210,16 @@ where
         where
             M: MapAccess<'de>,
         {
-            let (key, value) = &mut map.next_entry::<&str, &str>()?.unwrap();
-            if key == &"$type" {
-                if value == &"Vector2" {
+            let (key, value) = map
+                .next_entry::<String, String>()?
+                .ok_or_else(|| de::Error::custom("empty map"))?;
+            if key == "$type" {
+                if value == "Vector2" {
                     return Ok(Range::Position(crate::Vector2::deserialize(
                         de::value::MapAccessDeserializer::new(map),
                     )?));
                 }
-                if value == &"HDRColor" {
+                if value == "HDRColor" {
                     return Ok(Range::Color(crate::HDRColor::deserialize(
                         de::value::MapAccessDeserializer::new(map),
                     )?));
@@ -0,0 +1,224 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::anim::{
+    InkAnimAnimationLibraryResource, InkAnimDefinition, InkAnimEvent, InkAnimSequence, Target,
+};
+
+use super::{Change, PropertyChange, properties, trimmed};
+
+/// a sequence, definition, interpolator or event added, removed or modified
+#[derive(Debug, Clone, Serialize)]
+pub struct AnimChange {
+    pub change: Change,
+    pub sequence: String,
+    /// definition index, in the most recent export containing it
+    pub definition: Option<usize>,
+    /// targeted widget path indexes, in the most recent export containing it
+    /// (none for sequences and targets without handle ID)
+    pub target: Option<Vec<usize>>,
+    /// what changed, e.g. "sequence", "definition", "target",
+    /// "transparency #2" or "marker Start #1"
+    pub subject: String,
+    pub properties: Vec<PropertyChange>,
+}
+
+/// for each new item, the old one sharing the same key and occurrence,
+/// alongside whether each old item was matched
+fn matching<K: PartialEq>(old: &[K], new: &[K]) -> (Vec<Option<usize>>, Vec<bool>) {
+    let mut taken = vec![false; old.len()];
+    let matched = new
+        .iter()
+        .map(|key| {
+            let found = (0..old.len()).find(|x| !taken[*x] && &old[*x] == key)?;
+            taken[found] = true;
+            Some(found)
+        })
+        .collect();
+    (matched, taken)
+}
+
+/// 1-based occurrence of each key among the previous ones
+fn occurrences(keys: &[String]) -> Vec<String> {
+    keys.iter()
+        .enumerate()
+        .map(|(idx, key)| {
+            let nth = keys[..=idx].iter().filter(|x| *x == key).count();
+            format!("{key} #{nth}")
+        })
+        .collect()
+}
+
+fn event_key(event: &InkAnimEvent) -> String {
+    match event {
+        InkAnimEvent::inkanimPlaySoundEvent(event) => {
+            format!("sound {}", event.sound_event_name.as_str())
+        }
+        InkAnimEvent::inkanimChangeStateEvent(event) => format!("state {}", event.state.as_str()),
+        InkAnimEvent::inkanimMarkerEvent(event) => format!("marker {}", event.marker_name.as_str()),
+        InkAnimEvent::Unknown => "unknown event".to_string(),
+    }
+}
+
+fn target_key(target: &Target) -> Option<Vec<usize>> {
+    match target {
+        Target::WithHandleId(handle) => Some(handle.data.path.clone()),
+        Target::WithoutHandleId(_) => None,
+    }
+}
+
+/// compare two lists of keyed items, matched by key then order
+fn items(
+    old: Vec<(String, Value)>,
+    new: Vec<(String, Value)>,
+    mut push: impl FnMut(Change, String, Vec<PropertyChange>),
+) {
+    let old_labels = occurrences(&old.iter().map(|(x, _)| x.clone()).collect::<Vec<_>>());
+    let new_labels = occurrences(&new.iter().map(|(x, _)| x.clone()).collect::<Vec<_>>());
+    let mut matched: Vec<Option<usize>> = vec![None; new.len()];
+    let mut taken = vec![false; old.len()];
+    // unchanged items first, so that adding or removing one does not shift the others
+    for exact in [true, false] {
+        for (idx, item) in new.iter().enumerate() {
+            if matched[idx].is_some() {
+                continue;
+            }
+            if let Some(found) = (0..old.len())
+                .find(|x| !taken[*x] && old[*x].0 == item.0 && (!exact || old[*x].1 == item.1))
+            {
+                matched[idx] = Some(found);
+                taken[found] = true;
+            }
+        }
+    }
+    for (idx, (_, value)) in new.iter().enumerate() {
+        match matched[idx] {
+            None => push(Change::Added, new_labels[idx].clone(), vec![]),
+            Some(found) => {
+                let properties = properties(&old[found].1, value);
+                if !properties.is_empty() {
+                    push(Change::Changed, new_labels[idx].clone(), properties);
+                }
+            }
+        }
+    }
+    for (idx, _) in taken.iter().enumerate().filter(|(_, x)| !**x) {
+        push(Change::Removed, old_labels[idx].clone(), vec![]);
+    }
+}
+
+fn diff_definitions(
+    old: &InkAnimDefinition,
+    new: &InkAnimDefinition,
+) -> Vec<(Change, String, Vec<PropertyChange>)> {
+    let mut out = vec![];
+    let interpolators = |x: &InkAnimDefinition| {
+        x.interpolators
+            .iter()
+            .map(|x| (x.data.property(), trimmed(&x.data)))
+            .collect::<Vec<_>>()
+    };
+    items(
+        interpolators(old),
+        interpolators(new),
+        |change, subject, properties| out.push((change, subject, properties)),
+    );
+    let events = |x: &InkAnimDefinition| {
+        x.events
+            .iter()
+            .map(|x| (event_key(&x.data), trimmed(&x.data)))
+            .collect::<Vec<_>>()
+    };
+    items(events(old), events(new), |change, subject, properties| {
+        out.push((change, subject, properties))
+    });
+    out
+}
+
+fn diff_sequences(
+    name: &str,
+    old: &InkAnimSequence,
+    new: &InkAnimSequence,
+    out: &mut Vec<AnimChange>,
+) {
+    let old_targets = old.targets.iter().map(target_key).collect::<Vec<_>>();
+    let new_targets = new.targets.iter().map(target_key).collect::<Vec<_>>();
+    let (mut matched, mut taken) = matching(&old_targets, &new_targets);
+    // same definition index with another target: retargeted
+    for idx in 0..new.definitions.len() {
+        if matched[idx].is_none() && idx < old.definitions.len() && !taken[idx] {
+            matched[idx] = Some(idx);
+            taken[idx] = true;
+        }
+    }
+    let change =
+        |change, definition, target: &Option<Vec<usize>>, subject: &str, properties| AnimChange {
+            change,
+            sequence: name.to_string(),
+            definition: Some(definition),
+            target: target.clone(),
+            subject: subject.to_string(),
+            properties,
+        };
+    for (idx, definition) in new.definitions.iter().enumerate() {
+        let target = new_targets.get(idx).cloned().flatten();
+        let Some(found) = matched[idx] else {
+            out.push(change(Change::Added, idx, &target, "definition", vec![]));
+            continue;
+        };
+        let previous = old_targets.get(found).cloned().flatten();
+        if previous != target {
+            out.push(change(
+                Change::Changed,
+                idx,
+                &target,
+                "target",
+                vec![PropertyChange {
+                    property: "path".to_string(),
+                    old: previous.map(Value::from),
+                    new: target.clone().map(Value::from),
+                }],
+            ));
+        }
+        for (kind, subject, properties) in
+            diff_definitions(&old.definitions[found].data, &definition.data)
+        {
+            out.push(change(kind, idx, &target, &subject, properties));
+        }
+    }
+    for (idx, _) in taken.iter().enumerate().filter(|(_, x)| !**x) {
+        let target = old_targets.get(idx).cloned().flatten();
+        out.push(change(Change::Removed, idx, &target, "definition", vec![]));
+    }
+}
+
+/// compare two exports of the same .inkanim, matching sequences by name,
+/// definitions by target path and interpolators by kind then order
+pub fn diff_anims(
+    old: &InkAnimAnimationLibraryResource,
+    new: &InkAnimAnimationLibraryResource,
+) -> Vec<AnimChange> {
+    let sequence = |change, name: &str| AnimChange {
+        change,
+        sequence: name.to_string(),
+        definition: None,
+        target: None,
+        subject: "sequence".to_string(),
+        properties: vec![],
+    };
+    let mut out = vec![];
+    for current in new.sequences.iter() {
+        match old.sequences.iter().find(|x| x.name() == current.name()) {
+            Some(previous) => {
+                diff_sequences(current.name(), &previous.data, &current.data, &mut out)
+            }
+            None => out.push(sequence(Change::Added, current.name())),
+        }
+    }
+    for previous in old.sequences.iter() {
+        if !new.sequences.iter().any(|x| x.name() == previous.name()) {
+            out.push(sequence(Change::Removed, previous.name()));
+        }
+    }
+    out
+}
@@ -0,0 +1,125 @@
+//! Semantic differences between two exports of the same resource,
+//! e.g. before and after a game patch.
+
+mod anim;
+mod widget;
+pub use anim::*;
+pub use widget::*;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::Fidelity;
+
+/// how an item differs between both exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Change {
+    Added,
+    Removed,
+    Moved,
+    Changed,
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Added => "added",
+                Self::Removed => "removed",
+                Self::Moved => "moved",
+                Self::Changed => "changed",
+            }
+        )
+    }
+}
+
+/// a single property with a different value,
+/// missing on either side when left to its default
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PropertyChange {
+    /// dotted property path, e.g. "layout.margin.left"
+    pub property: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// trimmed JSON for the typed struct, which leaves out handle IDs and defaults
+pub(crate) fn trimmed<T: Serialize>(value: &T) -> Value {
+    let mut value = Fidelity::Trimmed
+        .scope(|| serde_json::to_value(value))
+        .expect("serialize to JSON");
+    narrow(&mut value);
+    value
+}
+
+/// write floats as the `f32` they were read from, e.g. `0.77` instead of `0.7699999809265137`
+fn narrow(value: &mut Value) {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            let float = number.as_f64().expect("f64") as f32;
+            if let Ok(narrowed) = float.to_string().parse::<serde_json::Number>() {
+                *number = narrowed;
+            }
+        }
+        Value::Object(map) => map.values_mut().for_each(narrow),
+        Value::Array(values) => values.iter_mut().for_each(narrow),
+        _ => {}
+    }
+}
+
+/// every leaf property differing between `old` and `new`
+pub fn properties(old: &Value, new: &Value) -> Vec<PropertyChange> {
+    fn visit(
+        prefix: &str,
+        old: Option<&Value>,
+        new: Option<&Value>,
+        out: &mut Vec<PropertyChange>,
+    ) {
+        if old == new {
+            return;
+        }
+        let key = |x: &str| match prefix {
+            "" => x.to_string(),
+            prefix => format!("{prefix}.{x}"),
+        };
+        match (old, new) {
+            (Some(Value::Object(old)), Some(Value::Object(new))) => {
+                for (name, value) in old.iter() {
+                    visit(&key(name), Some(value), new.get(name), out);
+                }
+                for (name, value) in new.iter().filter(|(x, _)| !old.contains_key(*x)) {
+                    visit(&key(name), None, Some(value), out);
+                }
+            }
+            (Some(Value::Array(old)), Some(Value::Array(new))) if old.len() == new.len() => {
+                for (idx, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+                    visit(&format!("{prefix}[{idx}]"), Some(old), Some(new), out);
+                }
+            }
+            (old, new) => out.push(PropertyChange {
+                property: prefix.to_string(),
+                old: old.cloned(),
+                new: new.cloned(),
+            }),
+        }
+    }
+    let mut out = vec![];
+    visit("", Some(old), Some(new), &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn floats_keep_their_f32_representation() {
+        let old = trimmed(&serde_json::json!({ "duration": 0.25_f32 }));
+        let new = trimmed(&serde_json::json!({ "duration": 0.77_f32 }));
+        assert_eq!(new.to_string(), r#"{"duration":0.77}"#);
+        assert_eq!(properties(&old, &new)[0].new, Some(new["duration"].clone()));
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    InkWrapper,
+    widget::{Classname, Walk, Widget, WidgetNode, inkWidgetLibraryResource},
+};
+
+use super::{Change, PropertyChange, properties, trimmed};
+
+/// where a widget sits in the tree
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Location {
+    /// full path names, with occurrence suffix wherever siblings share a name
+    pub names: Vec<String>,
+    pub indexes: Vec<usize>,
+}
+
+/// a widget added, removed, moved or modified
+#[derive(Debug, Clone, Serialize)]
+pub struct WidgetChange {
+    pub change: Change,
+    pub classname: String,
+    pub old: Option<Location>,
+    pub new: Option<Location>,
+    /// own properties only, excluding children
+    pub properties: Vec<PropertyChange>,
+}
+
+impl WidgetChange {
+    /// most recent location
+    pub fn location(&self) -> &Location {
+        self.new
+            .as_ref()
+            .or(self.old.as_ref())
+            .expect("either side")
+    }
+}
+
+struct Entry<'a> {
+    location: Location,
+    widget: &'a InkWrapper<Widget>,
+}
+
+/// every widget alongside its unambiguous location, in document order
+fn entries(resource: &inkWidgetLibraryResource) -> Vec<Entry<'_>> {
+    let nodes = resource.walk();
+    let key = |node: &WidgetNode| {
+        let (_, parent) = node.indexes.split_last().expect("at least one index");
+        (
+            parent.to_vec(),
+            node.names.last().cloned().unwrap_or_default(),
+        )
+    };
+    let mut totals: HashMap<(Vec<usize>, String), usize> = HashMap::new();
+    for node in nodes.iter() {
+        *totals.entry(key(node)).or_default() += 1;
+    }
+    let mut seen: HashMap<(Vec<usize>, String), usize> = HashMap::new();
+    let mut names: HashMap<Vec<usize>, Vec<String>> = HashMap::new();
+    let mut out = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let (parent, name) = key(&node);
+        let mut path = names.get(&parent).cloned().unwrap_or_default();
+        path.push(match totals[&(parent.clone(), name.clone())] {
+            1 => name.clone(),
+            _ => {
+                let nth = seen.entry((parent, name.clone())).or_default();
+                *nth += 1;
+                format!("{name}[{nth}]")
+            }
+        });
+        names.insert(node.indexes.clone(), path.clone());
+        out.push(Entry {
+            location: Location {
+                names: path,
+                indexes: node.indexes,
+            },
+            widget: node.widget,
+        });
+    }
+    out
+}
+
+/// own properties, without the children
+fn own(entry: &Entry) -> Value {
+    let mut value = trimmed(&entry.widget.data);
+    if let Some(object) = value.as_object_mut() {
+        object.remove("children");
+    }
+    value
+}
+
+/// positions of the longest strictly increasing subsequence of `(previous, current)` indexes,
+/// preferring those which kept the same index on ties
+fn longest_increasing(values: &[(usize, usize)]) -> Vec<usize> {
+    let score = |(previous, current): (usize, usize)| (1, usize::from(previous == current));
+    let mut scores = values.iter().map(|x| score(*x)).collect::<Vec<_>>();
+    let mut before: Vec<Option<usize>> = vec![None; values.len()];
+    for i in 0..values.len() {
+        for j in 0..i {
+            let (length, stable) = scores[j];
+            let (own_length, own_stable) = score(values[i]);
+            let candidate = (length + own_length, stable + own_stable);
+            if values[j].0 < values[i].0 && candidate > scores[i] {
+                scores[i] = candidate;
+                before[i] = Some(j);
+            }
+        }
+    }
+    let mut out = vec![];
+    let mut current = (0..values.len()).max_by_key(|x| scores[*x]);
+    while let Some(idx) = current {
+        out.push(idx);
+        current = before[idx];
+    }
+    out
+}
+
+/// compare two exports of the same .inkwidget, matching widgets by name path
+/// (falling back to handle ID), in new document order followed by removals
+pub fn diff_widgets(
+    old: &inkWidgetLibraryResource,
+    new: &inkWidgetLibraryResource,
+) -> Vec<WidgetChange> {
+    let old = entries(old);
+    let new = entries(new);
+    let mut matched: Vec<Option<usize>> = vec![None; new.len()];
+    let mut taken = vec![false; old.len()];
+    for (idx, entry) in new.iter().enumerate() {
+        if let Some(found) = old
+            .iter()
+            .position(|x| x.location.names == entry.location.names)
+        {
+            matched[idx] = Some(found);
+            taken[found] = true;
+        }
+    }
+    for (idx, entry) in new.iter().enumerate() {
+        if matched[idx].is_some() {
+            continue;
+        }
+        if let Some(found) = old
+            .iter()
+            .enumerate()
+            .position(|(i, x)| !taken[i] && x.widget.handle_id == entry.widget.handle_id)
+        {
+            matched[idx] = Some(found);
+            taken[found] = true;
+        }
+    }
+    // parent, as position in the list, by position in the list
+    let parents = |entries: &[Entry]| {
+        let positions = entries
+            .iter()
+            .enumerate()
+            .map(|(idx, x)| (x.location.indexes.clone(), idx))
+            .collect::<HashMap<_, _>>();
+        entries
+            .iter()
+            .map(|x| {
+                let (_, parent) = x.location.indexes.split_last().expect("at least one index");
+                positions.get(parent).copied()
+            })
+            .collect::<Vec<_>>()
+    };
+    let (old_parents, new_parents) = (parents(&old), parents(&new));
+    // renaming an ancestor changes the name path without moving anything
+    let reparented = (0..new.len())
+        .map(|idx| {
+            matched[idx].is_some_and(|found| {
+                new_parents[idx].and_then(|x| matched[x]) != old_parents[found]
+            })
+        })
+        .collect::<Vec<_>>();
+    // inserting or removing a sibling shifts the others without moving them either,
+    // so only siblings out of their previous relative order count as moved
+    let mut reordered = vec![false; new.len()];
+    let mut siblings: HashMap<Option<usize>, Vec<usize>> = HashMap::new();
+    for idx in (0..new.len()).filter(|x| matched[*x].is_some() && !reparented[*x]) {
+        siblings.entry(new_parents[idx]).or_default().push(idx);
+    }
+    for siblings in siblings.values() {
+        let positions = siblings
+            .iter()
+            .map(|x| {
+                let previous = &old[matched[*x].expect("matched above")].location.indexes;
+                let current = &new[*x].location.indexes;
+                (
+                    previous.last().copied().unwrap_or_default(),
+                    current.last().copied().unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let kept = longest_increasing(&positions);
+        for (position, idx) in siblings.iter().enumerate() {
+            reordered[*idx] = !kept.contains(&position);
+        }
+    }
+    let mut changes = vec![];
+    for (idx, entry) in new.iter().enumerate() {
+        let Some(found) = matched[idx] else {
+            changes.push(WidgetChange {
+                change: Change::Added,
+                classname: entry.widget.data.classname(),
+                old: None,
+                new: Some(entry.location.clone()),
+                properties: vec![],
+            });
+            continue;
+        };
+        let previous = &old[found];
+        let properties = properties(&own(previous), &own(entry));
+        let change = if reparented[idx] || reordered[idx] {
+            Change::Moved
+        } else if !properties.is_empty() {
+            Change::Changed
+        } else {
+            continue;
+        };
+        changes.push(WidgetChange {
+            change,
+            classname: entry.widget.data.classname(),
+            old: Some(previous.location.clone()),
+            new: Some(entry.location.clone()),
+            properties,
+        });
+    }
+    for (entry, _) in old.iter().zip(taken).filter(|(_, taken)| !taken) {
+        changes.push(WidgetChange {
+            change: Change::Removed,
+            classname: entry.widget.data.classname(),
+            old: Some(entry.location.clone()),
+            new: None,
+            properties: vec![],
+        });
+    }
+    changes
+}
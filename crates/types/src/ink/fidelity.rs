@@ -0,0 +1,114 @@
+//! Choosing between trimmed output (easier to read)
+//! and full-fidelity output (which WolvenKit can import back).
+
+use std::cell::Cell;
+
+use serde::{Deserialize, Serialize, ser::SerializeMap};
+
+thread_local! {
+    static FIDELITY: Cell<Fidelity> = const { Cell::new(Fidelity::Trimmed) };
+}
+
+/// how types get serialized
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Fidelity {
+    /// bare names, no handle IDs and no default values
+    #[default]
+    Trimmed,
+    /// exact WolvenKit JSON schema, ready to be imported back
+    WolvenKit,
+}
+
+impl Fidelity {
+    /// mode currently in use on this thread
+    pub fn current() -> Self {
+        FIDELITY.with(|x| x.get())
+    }
+
+    /// serialize with this mode within `f`
+    ///
+    /// e.g. `Fidelity::WolvenKit.scope(|| serde_json::to_string_pretty(&file))`
+    pub fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        let previous = FIDELITY.with(|x| x.replace(self));
+        let out = f();
+        FIDELITY.with(|x| x.set(previous));
+        out
+    }
+
+    pub(crate) fn trimmed() -> bool {
+        Self::current() == Self::Trimmed
+    }
+}
+
+/// fields not (yet) modelled, kept as-is to be written back
+///
+/// `$type` is left to the owning type.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Extras(pub(crate) serde_json::Map<String, serde_json::Value>);
+
+impl Extras {
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.get(key)
+    }
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut serde_json::Value> {
+        self.0.get_mut(key)
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut serde_json::Value)> {
+        self.0.iter_mut()
+    }
+}
+
+impl<'de> Deserialize<'de> for Extras {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut map = serde_json::Map::deserialize(deserializer)?;
+        map.remove("$type");
+        Ok(Self(map))
+    }
+}
+
+impl Serialize for Extras {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if Fidelity::trimmed() {
+            return serializer.serialize_map(Some(0))?.end();
+        }
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in self.0.iter() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// WolvenKit stores booleans as `0` or `1`
+pub(crate) fn serialize_bool<S>(value: &bool, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if Fidelity::trimmed() {
+        serializer.serialize_bool(*value)
+    } else {
+        serializer.serialize_u8(*value as u8)
+    }
+}
+
+#[cfg(feature = "clap")]
+impl clap::ValueEnum for Fidelity {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Trimmed, Self::WolvenKit]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        match self {
+            Self::Trimmed => Some(clap::builder::PossibleValue::new("trimmed")),
+            Self::WolvenKit => Some(
+                clap::builder::PossibleValue::new("wolvenkit").aliases(["import-ready", "wkit"]),
+            ),
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Handle IDs allocation and renumbering.
+//!
+//! Every `{ "HandleId", "Data" }` declares a handle, which later `{ "HandleRefId" }` point at.
+//! Handles are numbered per CR2W file: embedded files (e.g. each widget library item package)
+//! have their own numbering.
+
+use std::collections::HashMap;
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+use crate::{Fidelity, File, HandleId, InkWrapper};
+
+const HANDLE_ID: &str = "HandleId";
+const HANDLE_REF_ID: &str = "HandleRefId";
+
+/// hands out handle IDs which do not clash with existing ones
+#[derive(Debug, Default, Clone)]
+pub struct HandleAllocator {
+    next: u32,
+}
+
+impl HandleAllocator {
+    /// starts right after the highest handle ID used anywhere in `resource`
+    pub fn over<T: Serialize>(resource: &T) -> Self {
+        let value = Fidelity::WolvenKit
+            .scope(|| serde_json::to_value(resource))
+            .expect("serialize to JSON");
+        let mut highest = None;
+        visit(&value, &mut |map| {
+            if let Some(id) = map.get(HANDLE_ID).and_then(parse) {
+                highest = highest.max(Some(id));
+            }
+        });
+        Self {
+            next: highest.map(|x| x as u32 + 1).unwrap_or_default(),
+        }
+    }
+
+    fn fresh(&mut self) -> u32 {
+        self.next += 1;
+        self.next - 1
+    }
+
+    pub fn allocate(&mut self) -> HandleId {
+        HandleId::from(self.fresh())
+    }
+
+    pub fn wrap<T>(&mut self, data: T) -> InkWrapper<T> {
+        InkWrapper {
+            handle_id: self.allocate(),
+            data,
+        }
+    }
+
+    /// give every handle declared in `value` a fresh ID, rewriting handle ref IDs accordingly
+    ///
+    /// e.g. before adding a sequence coming from another resource
+    pub fn adopt<T: Serialize + DeserializeOwned>(&mut self, value: T) -> T {
+        let mut json = Fidelity::WolvenKit
+            .scope(|| serde_json::to_value(&value))
+            .expect("serialize to JSON");
+        renumber_with(&mut json, || self.fresh());
+        serde_json::from_value(json).expect("deserialize from JSON")
+    }
+}
+
+pub(crate) fn parse(value: &Value) -> Option<i64> {
+    match value {
+        Value::String(x) => x.parse().ok(),
+        Value::Number(x) => x.as_i64(),
+        _ => None,
+    }
+}
+
+/// start of an embedded CR2W file, or package
+pub(crate) fn is_file(map: &Map<String, Value>) -> bool {
+    map.contains_key("RootChunk") || map.contains_key("Chunks")
+}
+
+fn visit(value: &Value, f: &mut impl FnMut(&Map<String, Value>)) {
+    match value {
+        Value::Object(map) => {
+            f(map);
+            map.values().for_each(|x| visit(x, f));
+        }
+        Value::Array(values) => values.iter().for_each(|x| visit(x, f)),
+        _ => {}
+    }
+}
+
+/// handles declared in this file, in document order (embedded files excluded)
+pub(crate) fn declared(value: &Value, root: bool, out: &mut Vec<i64>) {
+    match value {
+        Value::Object(map) if !root && is_file(map) => {}
+        Value::Object(map) => {
+            if let Some(id) = map.get(HANDLE_ID).and_then(parse) {
+                out.push(id);
+            }
+            map.values().for_each(|x| declared(x, false, out));
+        }
+        Value::Array(values) => values.iter().for_each(|x| declared(x, false, out)),
+        _ => {}
+    }
+}
+
+/// new handle IDs, for each declaration in document order
+struct Renumbering {
+    /// one for each declaration occurrence
+    ids: Vec<u32>,
+    next: usize,
+    /// new ID of the nearest preceding declaration, for each old ID
+    current: HashMap<i64, u32>,
+    /// new ID of the first declaration, for refs preceding every declaration
+    first: HashMap<i64, u32>,
+}
+
+impl Renumbering {
+    fn resolve(&self, old: i64) -> Option<u32> {
+        self.current
+            .get(&old)
+            .or_else(|| self.first.get(&old))
+            .copied()
+    }
+}
+
+fn rewrite(value: &mut Value, root: bool, renumbering: &mut Renumbering) {
+    match value {
+        Value::Object(map) if !root && is_file(map) => renumber_file(value),
+        Value::Object(map) => {
+            // handle IDs can be duplicated, so each declaration gets its own
+            if let Some(old) = map.get(HANDLE_ID).and_then(parse) {
+                let id = renumbering.ids[renumbering.next];
+                renumbering.next += 1;
+                renumbering.current.insert(old, id);
+                map.insert(HANDLE_ID.to_string(), Value::String(id.to_string()));
+            }
+            // negative ref IDs stand for null handles, left untouched like dangling ones
+            if let Some(id) = map
+                .get(HANDLE_REF_ID)
+                .and_then(parse)
+                .and_then(|x| renumbering.resolve(x))
+            {
+                map.insert(HANDLE_REF_ID.to_string(), Value::String(id.to_string()));
+            }
+            map.values_mut()
+                .for_each(|x| rewrite(x, false, renumbering));
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|x| rewrite(x, false, renumbering)),
+        _ => {}
+    }
+}
+
+/// give each handle declared in this file an ID from `fresh`, in document order,
+/// pointing every handle ref ID at the nearest preceding declaration
+fn renumber_with(value: &mut Value, mut fresh: impl FnMut() -> u32) {
+    let mut declarations = vec![];
+    declared(value, true, &mut declarations);
+    let ids: Vec<u32> = declarations.iter().map(|_| fresh()).collect();
+    let mut first = HashMap::new();
+    for (old, new) in declarations.into_iter().zip(ids.iter()) {
+        first.entry(old).or_insert(*new);
+    }
+    rewrite(
+        value,
+        true,
+        &mut Renumbering {
+            ids,
+            next: 0,
+            current: HashMap::new(),
+            first,
+        },
+    );
+}
+
+fn renumber_file(value: &mut Value) {
+    let mut next = 0;
+    renumber_with(value, || {
+        next += 1;
+        next - 1
+    });
+}
+
+impl<T> File<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    /// renumber handle IDs from `0` in document order, once for each embedded file,
+    /// rewriting every handle ref ID accordingly
+    pub fn renumber(&mut self) -> Result<(), serde_json::Error> {
+        let mut value = Fidelity::WolvenKit.scope(|| serde_json::to_value(&*self))?;
+        renumber_file(&mut value);
+        *self = serde_json::from_value(value)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn duplicated_handle_ids() {
+        let mut value = json!({
+            "RootChunk": {
+                "forward": { "HandleRefId": "7" },
+                "first": { "HandleId": "7", "Data": {} },
+                "refs": [{ "HandleRefId": "7" }, { "HandleRefId": "-1" }],
+                "second": { "HandleId": "7", "Data": { "self": { "HandleRefId": "7" } } },
+                "last": { "HandleRefId": "7" },
+                "embedded": {
+                    "RootChunk": {
+                        "inner": { "HandleId": "7", "Data": {} },
+                        "ref": { "HandleRefId": "7" }
+                    }
+                }
+            }
+        });
+        renumber_file(&mut value);
+        assert_eq!(
+            value,
+            json!({
+                "RootChunk": {
+                    "forward": { "HandleRefId": "0" },
+                    "first": { "HandleId": "0", "Data": {} },
+                    "refs": [{ "HandleRefId": "0" }, { "HandleRefId": "-1" }],
+                    "second": { "HandleId": "1", "Data": { "self": { "HandleRefId": "1" } } },
+                    "last": { "HandleRefId": "1" },
+                    "embedded": {
+                        "RootChunk": {
+                            "inner": { "HandleId": "0", "Data": {} },
+                            "ref": { "HandleRefId": "0" }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Structural consistency checks between a widget and its animations,
+//! catching mistakes which would otherwise only show up in-game.
+
+use serde::Serialize;
+
+use crate::{
+    Fidelity,
+    anim::{InkAnimAnimationLibraryResource, InkAnimInterpolator, InkAnimInterpolatorType, Target},
+    widget::{Classname, inkWidgetLibraryResource},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// what gets checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// as many sequences as widget library items
+    LibraryCount,
+    /// as many definitions as targets, in each sequence
+    DefinitionsTargets,
+    /// every target path leads to a widget
+    UnresolvedTarget,
+    /// targets without handle ID only declare events
+    BlankTarget,
+    /// interpolator kind applies to the targeted widget class
+    InterpolatorKind,
+    /// effect interpolators animate an effect declared on the targeted widget
+    MissingEffect,
+    /// non-additive interpolators do not overlap on the same property of the same widget
+    ConflictingInterpolators,
+    /// siblings have distinct names, otherwise name paths are ambiguous
+    DuplicateName,
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_value(self)
+                .ok()
+                .and_then(|x| x.as_str().map(str::to_string))
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// a single issue found
+#[derive(Debug, Clone, Serialize)]
+pub struct Lint {
+    pub rule: Rule,
+    pub severity: Severity,
+    /// sequence name, when related
+    pub sequence: Option<String>,
+    /// definition index in sequence, when related
+    pub definition: Option<usize>,
+    pub message: String,
+}
+
+impl Lint {
+    fn error(rule: Rule, message: String) -> Self {
+        Self {
+            rule,
+            severity: Severity::Error,
+            sequence: None,
+            definition: None,
+            message,
+        }
+    }
+    fn warning(rule: Rule, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(rule, message)
+        }
+    }
+    fn at(mut self, sequence: &str, definition: Option<usize>) -> Self {
+        self.sequence = Some(sequence.to_string());
+        self.definition = definition;
+        self
+    }
+}
+
+fn dotted(path: &[usize]) -> String {
+    path.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// interpolators which only apply to a given widget class
+const TEXT_ONLY: &[InkAnimInterpolatorType] = &[
+    InkAnimInterpolatorType::TextValueProgress,
+    InkAnimInterpolatorType::TextReplace,
+    InkAnimInterpolatorType::TextOffset,
+];
+
+/// check every sequence against the widget tree
+pub fn lint(
+    widget: &inkWidgetLibraryResource,
+    anim: &InkAnimAnimationLibraryResource,
+) -> Vec<Lint> {
+    let mut lints = vec![];
+    if anim.sequences.len() != widget.library_items.len() {
+        lints.push(Lint::error(
+            Rule::LibraryCount,
+            format!(
+                "{} sequence(s) for {} widget library item(s)",
+                anim.sequences.len(),
+                widget.library_items.len()
+            ),
+        ));
+    }
+    for duplicate in widget.duplicate_names() {
+        let parent = match duplicate.parent.as_slice() {
+            [] => "root".to_string(),
+            parent => dotted(parent),
+        };
+        lints.push(Lint::warning(
+            Rule::DuplicateName,
+            format!(
+                "{} siblings named {} under {parent} (at {}), address them as {}[1] to {}[{}]",
+                duplicate.siblings.len(),
+                duplicate.name,
+                duplicate
+                    .siblings
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                duplicate.name,
+                duplicate.name,
+                duplicate.siblings.len()
+            ),
+        ));
+    }
+    for sequence in anim.sequences.iter() {
+        let name = sequence.name();
+        let definitions = &sequence.data.definitions;
+        let targets = &sequence.data.targets;
+        if definitions.len() != targets.len() {
+            lints.push(
+                Lint::error(
+                    Rule::DefinitionsTargets,
+                    format!(
+                        "{} definition(s) for {} target(s)",
+                        definitions.len(),
+                        targets.len()
+                    ),
+                )
+                .at(name, None),
+            );
+        }
+        for (idx, (definition, target)) in definitions.iter().zip(targets.iter()).enumerate() {
+            let interpolators = &definition.data.interpolators;
+            let path = match target {
+                Target::WithoutHandleId(_) => {
+                    if !interpolators.is_empty() {
+                        lints.push(
+                            Lint::error(
+                                Rule::BlankTarget,
+                                format!(
+                                    "blank target owns {} interpolator(s), instead of events only",
+                                    interpolators.len()
+                                ),
+                            )
+                            .at(name, Some(idx)),
+                        );
+                    }
+                    continue;
+                }
+                Target::WithHandleId(handle) => &handle.data.path,
+            };
+            let Some(target) = widget.widget_at(path) else {
+                lints.push(
+                    Lint::error(
+                        Rule::UnresolvedTarget,
+                        format!("no widget at {}", dotted(path)),
+                    )
+                    .at(name, Some(idx)),
+                );
+                continue;
+            };
+            let classname = target.data.classname();
+            for (idx_interpolator, interpolator) in interpolators.iter().enumerate() {
+                let interpolator = &interpolator.data;
+                if classname != "inkTextWidget" && TEXT_ONLY.iter().any(|x| interpolator == x) {
+                    lints.push(
+                        Lint::error(
+                            Rule::InterpolatorKind,
+                            format!(
+                                "interpolator {idx_interpolator} only applies to inkTextWidget, not {classname} at {}",
+                                dotted(path)
+                            ),
+                        )
+                        .at(name, Some(idx)),
+                    );
+                }
+                if let InkAnimInterpolator::inkanimEffectInterpolator(effect) = interpolator {
+                    let class = format!("ink{:?}Effect", effect.effect_type);
+                    let effects = Fidelity::WolvenKit
+                        .scope(|| serde_json::to_value(&target.data))
+                        .expect("serialize to JSON");
+                    let declared = effects["effects"].as_array().is_some_and(|x| {
+                        x.iter().any(|x| {
+                            x["Data"]["$type"] == class.as_str()
+                                && x["Data"]["effectName"]["$value"] == effect.effect_name.as_str()
+                        })
+                    });
+                    if !declared {
+                        lints.push(
+                            Lint::error(
+                                Rule::MissingEffect,
+                                format!(
+                                    "interpolator {idx_interpolator} animates {class} {} missing on {classname} at {}",
+                                    effect.effect_name.as_str(),
+                                    dotted(path)
+                                ),
+                            )
+                            .at(name, Some(idx)),
+                        );
+                    }
+                }
+            }
+        }
+        for conflict in sequence.data.conflicts().iter().filter(|x| x.is_fight()) {
+            let [first, second] = conflict.interpolators;
+            lints.push(
+                Lint::warning(
+                    Rule::ConflictingInterpolators,
+                    format!(
+                        "interpolators {first} and {second} both animate {} at {} between {}s and {}s",
+                        conflict.property,
+                        dotted(&conflict.path),
+                        conflict.window.start,
+                        conflict.window.end
+                    ),
+                )
+                .at(name, None),
+            );
+        }
+    }
+    lints
+}
@@ -0,0 +1,740 @@
+//! Structural three-way merge of WolvenKit JSON exports,
+//! e.g. as a git merge driver.
+//!
+//! Lists of handles (widgets, sequences, interpolators, events...) are matched
+//! by name (or kind, for anonymous ones) instead of by position, and handles only
+//! added on their side get fresh IDs, so that both sides can add widgets
+//! without clashing.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use crate::{HandleAllocator, declared, is_file, parse};
+
+const HANDLE_ID: &str = "HandleId";
+const HANDLE_REF_ID: &str = "HandleRefId";
+/// fields telling apart items in a list, when unnamed
+const IDENTITIES: &[&str] = &[
+    "effectName",
+    "paramName",
+    "soundEventName",
+    "state",
+    "markerName",
+];
+
+/// both sides changed the same value differently
+#[derive(Debug, Clone)]
+pub struct MergeConflict {
+    /// e.g. "RootChunk.libraryItems[Root].package.File.RootChunk.rootWidget.children.children[MAIN_CANVAS].layout"
+    pub path: String,
+    /// missing when added on both sides
+    pub base: Option<Value>,
+    /// kept in the merged output, missing when removed
+    pub ours: Option<Value>,
+    /// missing when removed
+    pub theirs: Option<Value>,
+}
+
+impl std::fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let show = |x: &Option<Value>| match x {
+            Some(Value::Object(_) | Value::Array(_)) => "(modified)".to_string(),
+            Some(x) => x.to_string(),
+            None => "(removed)".to_string(),
+        };
+        write!(
+            f,
+            "{}: ours {}, theirs {}",
+            self.path,
+            show(&self.ours),
+            show(&self.theirs)
+        )
+    }
+}
+
+/// value of a name field, either bare or wrapped in `{ "$value" }`
+fn text(value: &Value) -> Option<&str> {
+    value
+        .as_str()
+        .or_else(|| value.get("$value").and_then(Value::as_str))
+}
+
+/// what identifies an item in a list, regardless of its position or handle ID
+fn identity(item: &Value) -> String {
+    let data = item.get("Data").filter(|x| x.is_object()).unwrap_or(item);
+    if let Some(name) = data.get("name").and_then(text) {
+        return name.to_string();
+    }
+    if let Some(kind) = data.get("$type").and_then(text) {
+        let mut key = kind.to_string();
+        for field in IDENTITIES {
+            if let Some(value) = data.get(*field).and_then(text) {
+                key.push(' ');
+                key.push_str(value);
+            }
+        }
+        return key;
+    }
+    item.get(HANDLE_ID)
+        .and_then(parse)
+        .map(|x| format!("#{x}"))
+        .unwrap_or_default()
+}
+
+/// lists whose items are matched by identity, otherwise merged as a whole
+fn is_keyed(items: &[Value]) -> bool {
+    !items.is_empty()
+        && items.iter().all(|x| {
+            x.as_object()
+                .is_some_and(|x| x.contains_key(HANDLE_ID) || x.contains_key("$type"))
+        })
+}
+
+/// identities suffixed with their occurrence, when repeated
+fn keys(items: &[Value], identity: impl Fn(&Value) -> String) -> Vec<String> {
+    let identities = items.iter().map(identity).collect::<Vec<_>>();
+    identities
+        .iter()
+        .enumerate()
+        .map(
+            |(idx, key)| match identities[..idx].iter().filter(|x| *x == key).count() {
+                0 => key.clone(),
+                nth => format!("{key}[{}]", nth + 1),
+            },
+        )
+        .collect()
+}
+
+/// equal regardless of handle IDs, which a re-export renumbers, and number formatting
+fn same(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            let relevant = |x: &&String| *x != HANDLE_ID && *x != HANDLE_REF_ID;
+            left.keys().filter(relevant).count() == right.keys().filter(relevant).count()
+                && left
+                    .iter()
+                    .filter(|(x, _)| relevant(x))
+                    .all(|(key, x)| right.get(key).is_some_and(|y| same(x, y)))
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            left.len() == right.len() && left.iter().zip(right).all(|(x, y)| same(x, y))
+        }
+        // e.g. `23` and `23.0`, depending on the exporter,
+        // compared as single precision like the game stores them
+        (Value::Number(left), Value::Number(right)) => {
+            left.as_f64().map(|x| x as f32) == right.as_f64().map(|x| x as f32)
+        }
+        (left, right) => left == right,
+    }
+}
+
+fn same_option(left: Option<&Value>, right: Option<&Value>) -> bool {
+    match (left, right) {
+        (Some(left), Some(right)) => same(left, right),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+struct Merger {
+    allocator: HandleAllocator,
+    /// their handle IDs to ours, for each embedded file being merged
+    scopes: Vec<HashMap<i64, i64>>,
+    conflicts: Vec<MergeConflict>,
+}
+
+impl Merger {
+    fn conflict(
+        &mut self,
+        path: &str,
+        base: Option<&Value>,
+        ours: Option<&Value>,
+        theirs: Option<&Value>,
+    ) {
+        self.conflicts.push(MergeConflict {
+            path: path.to_string(),
+            base: base.cloned(),
+            ours: ours.cloned(),
+            theirs: theirs.cloned(),
+        });
+    }
+
+    /// bring in a value only found on their side:
+    /// point its refs at our handles, then give its own handles fresh IDs
+    fn import(&mut self, value: &Value) -> Value {
+        let mut value = value.clone();
+        let mut own = vec![];
+        declared(&value, false, &mut own);
+        if let Some(mapping) = self.scopes.last() {
+            translate(&mut value, &own, mapping);
+        }
+        if own.is_empty() {
+            return value;
+        }
+        self.allocator.adopt(value)
+    }
+
+    fn value(
+        &mut self,
+        path: &str,
+        base: Option<&Value>,
+        ours: Option<&Value>,
+        theirs: Option<&Value>,
+    ) -> Option<Value> {
+        match (base, ours, theirs) {
+            (_, Some(Value::Object(ours)), Some(Value::Object(theirs))) => Some(Value::Object(
+                self.object(path, base.and_then(Value::as_object), ours, theirs),
+            )),
+            (_, Some(Value::Array(ours)), Some(Value::Array(theirs)))
+                if is_keyed(ours) || is_keyed(theirs) =>
+            {
+                let base = base.and_then(Value::as_array).map(Vec::as_slice);
+                Some(Value::Array(self.list(
+                    path,
+                    base.unwrap_or_default(),
+                    ours,
+                    theirs,
+                    identity,
+                )))
+            }
+            _ if same_option(ours, theirs) => ours.cloned(),
+            _ if same_option(base, ours) => theirs.map(|x| self.import(x)),
+            _ if same_option(base, theirs) => ours.cloned(),
+            _ => {
+                self.conflict(path, base, ours, theirs);
+                ours.cloned()
+            }
+        }
+    }
+
+    fn object(
+        &mut self,
+        path: &str,
+        base: Option<&Map<String, Value>>,
+        ours: &Map<String, Value>,
+        theirs: &Map<String, Value>,
+    ) -> Map<String, Value> {
+        let file = is_file(ours);
+        if file {
+            self.scopes.push(HashMap::new());
+        }
+        if let (Some(mine), Some(their)) = (
+            ours.get(HANDLE_ID).and_then(parse),
+            theirs.get(HANDLE_ID).and_then(parse),
+        ) && let Some(scope) = self.scopes.last_mut()
+        {
+            scope.insert(their, mine);
+        }
+        let mut out = Map::new();
+        let mut keys = ours.keys().collect::<Vec<_>>();
+        keys.extend(theirs.keys().filter(|x| !ours.contains_key(*x)));
+        for key in keys {
+            let nested = match key.as_str() {
+                "Data" => path.to_string(),
+                key if path.is_empty() => key.to_string(),
+                key => format!("{path}.{key}"),
+            };
+            let (base_value, our_value, their_value) = (
+                base.and_then(|x| x.get(key)),
+                ours.get(key),
+                theirs.get(key),
+            );
+            let merged = match key.as_str() {
+                // renumbered on re-export, ours prevails
+                HANDLE_ID => our_value.cloned(),
+                // export metadata (e.g. date), ours prevails too
+                "Header" if path.is_empty() => our_value.cloned(),
+                HANDLE_REF_ID => match (our_value, their_value) {
+                    (Some(mine), Some(their))
+                        if base_value == Some(mine) && base_value != Some(their) =>
+                    {
+                        let mapped = parse(their)
+                            .and_then(|x| self.scopes.last().and_then(|s| s.get(&x)).copied())
+                            .map(|x| Value::String(x.to_string()));
+                        mapped.or_else(|| Some(their.clone()))
+                    }
+                    _ => our_value.or(their_value).cloned(),
+                },
+                // definitions only make sense alongside their targets
+                "definitions" if ours.contains_key("targets") => {
+                    self.definitions(&nested, base, ours, theirs, &mut out);
+                    continue;
+                }
+                "targets" if ours.contains_key("definitions") => continue,
+                _ => self.value(&nested, base_value, our_value, their_value),
+            };
+            if let Some(merged) = merged {
+                out.insert(key.clone(), merged);
+            }
+        }
+        if file {
+            self.scopes.pop();
+        }
+        out
+    }
+
+    /// merge definitions and targets as pairs, matched by target path
+    fn definitions(
+        &mut self,
+        path: &str,
+        base: Option<&Map<String, Value>>,
+        ours: &Map<String, Value>,
+        theirs: &Map<String, Value>,
+        out: &mut Map<String, Value>,
+    ) {
+        let pairs = |x: Option<&Map<String, Value>>| {
+            let Some(x) = x else {
+                return vec![];
+            };
+            let empty = vec![];
+            let definitions = x
+                .get("definitions")
+                .and_then(Value::as_array)
+                .unwrap_or(&empty);
+            let targets = x.get("targets").and_then(Value::as_array).unwrap_or(&empty);
+            definitions
+                .iter()
+                .zip(targets.iter())
+                .map(|(definition, target)| {
+                    let mut pair = Map::new();
+                    pair.insert("definition".to_string(), definition.clone());
+                    pair.insert("target".to_string(), target.clone());
+                    Value::Object(pair)
+                })
+                .collect::<Vec<_>>()
+        };
+        let target = |pair: &Value| match pair["target"]["Data"]["path"].as_array() {
+            Some(path) => path
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join("."),
+            None => "blank".to_string(),
+        };
+        let merged = self.list(
+            path,
+            &pairs(base),
+            &pairs(Some(ours)),
+            &pairs(Some(theirs)),
+            target,
+        );
+        let (definitions, targets): (Vec<_>, Vec<_>) = merged
+            .into_iter()
+            .map(|mut x| (x["definition"].take(), x["target"].take()))
+            .unzip();
+        out.insert("definitions".to_string(), Value::Array(definitions));
+        out.insert("targets".to_string(), Value::Array(targets));
+    }
+
+    /// merge items matched by identity, in our order,
+    /// with their additions following the same item as on their side
+    fn list(
+        &mut self,
+        path: &str,
+        base: &[Value],
+        ours: &[Value],
+        theirs: &[Value],
+        identity: impl Fn(&Value) -> String,
+    ) -> Vec<Value> {
+        let base_keys = keys(base, &identity);
+        let our_keys = renamed(keys(ours, &identity), ours, &base_keys, base);
+        let their_keys = renamed(keys(theirs, &identity), theirs, &base_keys, base);
+        let find = |keys: &[String], key: &String| keys.iter().position(|x| x == key);
+        let mut out: Vec<(String, Value)> = vec![];
+        for (key, mine) in our_keys.iter().zip(ours) {
+            let nested = format!("{path}[{key}]");
+            let previous = find(&base_keys, key).map(|x| &base[x]);
+            match find(&their_keys, key).map(|x| &theirs[x]) {
+                Some(their) => {
+                    if let Some(merged) = self.value(&nested, previous, Some(mine), Some(their)) {
+                        out.push((key.clone(), merged));
+                    }
+                }
+                // removed on their side
+                None => match previous {
+                    Some(previous) if same(previous, mine) => {}
+                    Some(previous) => {
+                        self.conflict(&nested, Some(previous), Some(mine), None);
+                        out.push((key.clone(), mine.clone()));
+                    }
+                    None => out.push((key.clone(), mine.clone())),
+                },
+            }
+        }
+        for (idx, (key, their)) in their_keys.iter().zip(theirs).enumerate() {
+            if find(&our_keys, key).is_some() {
+                continue;
+            }
+            // removed on our side
+            if let Some(previous) = find(&base_keys, key).map(|x| &base[x]) {
+                if !same(previous, their) {
+                    self.conflict(&format!("{path}[{key}]"), Some(previous), None, Some(their));
+                }
+                continue;
+            }
+            let after = their_keys[..idx]
+                .iter()
+                .rev()
+                .find_map(|x| out.iter().position(|(y, _)| y == x));
+            let imported = self.import(their);
+            out.insert(
+                after.map(|x| x + 1).unwrap_or_default(),
+                (key.clone(), imported),
+            );
+        }
+        // follow their order when only they reordered shared items
+        let shared = |keys: &[String]| {
+            keys.iter()
+                .filter(|x| base_keys.contains(x) && our_keys.contains(x) && their_keys.contains(x))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let (previous, mine, their) = (shared(&base_keys), shared(&our_keys), shared(&their_keys));
+        if mine != their && mine != previous {
+            if their != previous {
+                self.conflict(
+                    &format!("{path} (order)"),
+                    Some(&Value::from(previous)),
+                    Some(&Value::from(mine)),
+                    Some(&Value::from(their)),
+                );
+            }
+        } else if mine != their {
+            // items only found on our side stay right after the same item
+            let attached = |rest: &[(String, Value)], from: usize| {
+                rest[from..]
+                    .iter()
+                    .take_while(|(x, _)| !their_keys.contains(x))
+                    .count()
+            };
+            let mut rest = out;
+            let mut sorted = rest.drain(..attached(&rest, 0)).collect::<Vec<_>>();
+            for key in their_keys.iter() {
+                let Some(idx) = rest.iter().position(|(x, _)| x == key) else {
+                    continue;
+                };
+                let end = idx + 1 + attached(&rest, idx + 1);
+                sorted.extend(rest.drain(idx..end));
+            }
+            sorted.append(&mut rest);
+            out = sorted;
+        }
+        out.into_iter().map(|(_, x)| x).collect()
+    }
+}
+
+/// items renamed on one side keep their previous key, when their handle ID did not change
+fn renamed(
+    mut keys: Vec<String>,
+    items: &[Value],
+    base_keys: &[String],
+    base: &[Value],
+) -> Vec<String> {
+    for idx in 0..keys.len() {
+        if base_keys.contains(&keys[idx]) {
+            continue;
+        }
+        let Some(id) = items[idx].get(HANDLE_ID).and_then(parse) else {
+            continue;
+        };
+        if let Some(previous) = base
+            .iter()
+            .position(|x| x.get(HANDLE_ID).and_then(parse) == Some(id))
+            && !keys.contains(&base_keys[previous])
+        {
+            keys[idx] = base_keys[previous].clone();
+        }
+    }
+    keys
+}
+
+/// point handle ref IDs at their counterparts, except those declared within
+fn translate(value: &mut Value, own: &[i64], mapping: &HashMap<i64, i64>) {
+    match value {
+        Value::Object(map) if is_file(map) => {}
+        Value::Object(map) => {
+            if let Some(id) = map.get(HANDLE_REF_ID).and_then(parse)
+                && !own.contains(&id)
+                && let Some(mapped) = mapping.get(&id)
+            {
+                map.insert(HANDLE_REF_ID.to_string(), Value::String(mapped.to_string()));
+            }
+            map.values_mut().for_each(|x| translate(x, own, mapping));
+        }
+        Value::Array(values) => values.iter_mut().for_each(|x| translate(x, own, mapping)),
+        _ => {}
+    }
+}
+
+/// merge `theirs` into `ours`, both derived from `base`,
+/// keeping our side on conflicts
+pub fn merge3(base: &Value, ours: &Value, theirs: &Value) -> (Value, Vec<MergeConflict>) {
+    let mut merger = Merger {
+        allocator: HandleAllocator::over(ours),
+        scopes: vec![HashMap::new()],
+        conflicts: vec![],
+    };
+    let merged = merger
+        .value("", Some(base), Some(ours), Some(theirs))
+        .unwrap_or(Value::Null);
+    (merged, merger.conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn text(id: u32, name: &str, parent: u32, content: &str) -> Value {
+        json!({
+            "HandleId": id.to_string(),
+            "Data": {
+                "$type": "inkTextWidget",
+                "name": name,
+                "text": content,
+                "parentWidget": { "HandleRefId": parent.to_string() }
+            }
+        })
+    }
+
+    fn canvas(id: u32, name: &str, parent: u32, children: Vec<Value>) -> Value {
+        json!({
+            "HandleId": id.to_string(),
+            "Data": {
+                "$type": "inkCanvasWidget",
+                "name": name,
+                "parentWidget": { "HandleRefId": parent.to_string() },
+                "children": children
+            }
+        })
+    }
+
+    fn file(children: Vec<Value>) -> Value {
+        json!({
+            "Header": { "ExportedDateTime": "2024-01-01" },
+            "Data": {
+                "RootChunk": {
+                    "rootWidget": {
+                        "HandleId": "0",
+                        "Data": { "$type": "inkCanvasWidget", "name": "root", "children": children }
+                    }
+                }
+            }
+        })
+    }
+
+    fn children(value: &Value) -> &Vec<Value> {
+        value["Data"]["RootChunk"]["rootWidget"]["Data"]["children"]
+            .as_array()
+            .unwrap()
+    }
+
+    fn names(value: &Value) -> Vec<&str> {
+        children(value)
+            .iter()
+            .map(|x| x["Data"]["name"].as_str().unwrap())
+            .collect()
+    }
+
+    fn base() -> Value {
+        file(vec![
+            text(1, "a", 0, "A"),
+            canvas(2, "b", 0, vec![]),
+            text(3, "c", 0, "C"),
+        ])
+    }
+
+    #[test]
+    fn unchanged() {
+        let (merged, conflicts) = merge3(&base(), &base(), &base());
+        assert!(conflicts.is_empty());
+        assert_eq!(merged, base());
+    }
+
+    #[test]
+    fn rename_and_edit() {
+        let ours = file(vec![
+            text(1, "renamed", 0, "A"),
+            canvas(2, "b", 0, vec![]),
+            text(3, "c", 0, "C"),
+        ]);
+        let theirs = file(vec![
+            text(1, "a", 0, "edited"),
+            canvas(2, "b", 0, vec![]),
+            text(3, "c", 0, "C"),
+        ]);
+        let (merged, conflicts) = merge3(&base(), &ours, &theirs);
+        assert!(conflicts.is_empty(), "{conflicts:?}");
+        assert_eq!(names(&merged), vec!["renamed", "b", "c"]);
+        assert_eq!(children(&merged)[0]["Data"]["text"], "edited");
+    }
+
+    #[test]
+    fn conflicting_renames() {
+        let renamed = |name| {
+            file(vec![
+                text(1, name, 0, "A"),
+                canvas(2, "b", 0, vec![]),
+                text(3, "c", 0, "C"),
+            ])
+        };
+        let (merged, conflicts) = merge3(&base(), &renamed("ours"), &renamed("theirs"));
+        assert_eq!(conflicts.len(), 1);
+        assert!(
+            conflicts[0].path.ends_with("[a].name"),
+            "{}",
+            conflicts[0].path
+        );
+        assert_eq!(conflicts[0].ours, Some(json!("ours")));
+        assert_eq!(conflicts[0].theirs, Some(json!("theirs")));
+        // ours prevails
+        assert_eq!(names(&merged), vec!["ours", "b", "c"]);
+    }
+
+    #[test]
+    fn removed_and_edited() {
+        let ours = file(vec![canvas(2, "b", 0, vec![]), text(3, "c", 0, "C")]);
+        let theirs = file(vec![
+            text(1, "a", 0, "edited"),
+            canvas(2, "b", 0, vec![]),
+            text(3, "c", 0, "C"),
+        ]);
+        let (merged, conflicts) = merge3(&base(), &ours, &theirs);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].ours, None);
+        assert_eq!(names(&merged), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn both_add_children() {
+        let ours = file(vec![
+            text(1, "a", 0, "A"),
+            canvas(2, "b", 0, vec![]),
+            text(3, "c", 0, "C"),
+            text(4, "ours", 0, "mine"),
+        ]);
+        // re-exported on their side: b got renumbered, and their additions reuse our IDs
+        let theirs = file(vec![
+            text(1, "a", 0, "A"),
+            text(4, "theirs", 0, "their"),
+            canvas(9, "b", 0, vec![text(5, "nested", 9, "N")]),
+            text(3, "c", 0, "C"),
+        ]);
+        let (merged, conflicts) = merge3(&base(), &ours, &theirs);
+        assert!(conflicts.is_empty(), "{conflicts:?}");
+        // their addition follows the same item as on their side
+        assert_eq!(names(&merged), vec!["a", "theirs", "b", "c", "ours"]);
+        let mut ids = vec![];
+        declared(&merged, true, &mut ids);
+        let mut unique = ids.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(ids.len(), unique.len(), "handle IDs clash: {ids:?}");
+        let added = &children(&merged)[1];
+        assert!(parse(&added["HandleId"]).unwrap() > 4);
+        assert_eq!(added["Data"]["parentWidget"]["HandleRefId"], "0");
+        let b = &children(&merged)[2];
+        assert_eq!(b["HandleId"], "2");
+        let nested = &b["Data"]["children"][0];
+        assert!(parse(&nested["HandleId"]).unwrap() > 4);
+        // pointed at our b, instead of their renumbered one
+        assert_eq!(nested["Data"]["parentWidget"]["HandleRefId"], "2");
+    }
+
+    #[test]
+    fn reordered_on_their_side() {
+        let theirs = file(vec![
+            text(3, "c", 0, "C"),
+            text(1, "a", 0, "A"),
+            canvas(2, "b", 0, vec![]),
+        ]);
+        let (merged, conflicts) = merge3(&base(), &base(), &theirs);
+        assert!(conflicts.is_empty(), "{conflicts:?}");
+        assert_eq!(names(&merged), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn reordered_on_both_sides() {
+        let ours = file(vec![
+            canvas(2, "b", 0, vec![]),
+            text(1, "a", 0, "A"),
+            text(3, "c", 0, "C"),
+        ]);
+        let theirs = file(vec![
+            text(1, "a", 0, "A"),
+            text(3, "c", 0, "C"),
+            canvas(2, "b", 0, vec![]),
+        ]);
+        let (merged, conflicts) = merge3(&base(), &ours, &theirs);
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].path.ends_with("(order)"));
+        assert_eq!(names(&merged), vec!["b", "a", "c"]);
+    }
+
+    fn sequence(pairs: &[(u32, &str, u32, u32)]) -> Value {
+        let (definitions, targets): (Vec<_>, Vec<_>) = pairs
+            .iter()
+            .map(|(id, tag, target, path)| {
+                (
+                    json!({
+                        "HandleId": id.to_string(),
+                        "Data": { "$type": "inkanimDefinition", "tag": tag }
+                    }),
+                    json!({
+                        "HandleId": target.to_string(),
+                        "Data": { "$type": "inkanimSequenceTargetInfo", "path": [0, path] }
+                    }),
+                )
+            })
+            .unzip();
+        json!({
+            "Header": { "ExportedDateTime": "2024-01-01" },
+            "Data": {
+                "RootChunk": {
+                    "sequences": [{
+                        "HandleId": "0",
+                        "Data": {
+                            "$type": "inkanimSequence",
+                            "name": "MAIN",
+                            "definitions": definitions,
+                            "targets": targets
+                        }
+                    }]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn definitions_stay_paired_with_targets() {
+        let base = sequence(&[(1, "first", 2, 0)]);
+        let ours = sequence(&[(1, "first", 2, 0), (3, "ours", 4, 1)]);
+        let theirs = sequence(&[(3, "theirs", 4, 2), (1, "edited", 2, 0)]);
+        let (merged, conflicts) = merge3(&base, &ours, &theirs);
+        assert!(conflicts.is_empty(), "{conflicts:?}");
+        let sequence = &merged["Data"]["RootChunk"]["sequences"][0]["Data"];
+        let definitions = sequence["definitions"].as_array().unwrap();
+        let targets = sequence["targets"].as_array().unwrap();
+        assert_eq!(definitions.len(), targets.len());
+        let pairs = definitions
+            .iter()
+            .zip(targets)
+            .map(|(definition, target)| {
+                (
+                    definition["Data"]["tag"].as_str().unwrap(),
+                    target["Data"]["path"][1].as_u64().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, vec![("theirs", 2), ("edited", 0), ("ours", 1)]);
+        // their pair got fresh handle IDs, instead of clashing with ours
+        let mut ids = vec![];
+        declared(&merged, true, &mut ids);
+        let mut unique = ids.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(ids.len(), unique.len(), "handle IDs clash: {ids:?}");
+    }
+}
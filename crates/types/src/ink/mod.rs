@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
-use serde::{Deserialize, Serialize, ser::SerializeStruct};
+use serde::{
+    Deserialize, Serialize,
+    ser::{SerializeMap, SerializeStruct},
+};
 use serde_aux::prelude::*;
 
 use self::{
@@ -9,9 +12,20 @@ use self::{
 };
 mod conversion;
 use conversion::deserialize_lockey_from_anything;
+mod fidelity;
+pub use fidelity::*;
+mod handle;
+pub use handle::*;
+mod lint;
+pub use lint::*;
+mod merge;
+pub use merge::*;
+mod transplant;
 
 /// everything related to *.inkanim*
 pub mod anim;
+/// semantic differences between exports
+pub mod diff;
 /// everything related to *.inkwidget*
 pub mod widget;
 
@@ -30,7 +44,35 @@ impl Serialize for Name {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.value)
+        if Fidelity::trimmed() {
+            return serializer.serialize_str(&self.value);
+        }
+        let mut s = serializer.serialize_struct("CName", 3)?;
+        s.serialize_field(
+            "$type",
+            if self.r#type.is_empty() {
+                "CName"
+            } else {
+                &self.r#type
+            },
+        )?;
+        s.serialize_field(
+            "$storage",
+            if self.storage.is_empty() {
+                "string"
+            } else {
+                &self.storage
+            },
+        )?;
+        s.serialize_field(
+            "$value",
+            if self.value.is_empty() {
+                "None"
+            } else {
+                &self.value
+            },
+        )?;
+        s.end()
     }
 }
 
@@ -40,6 +82,16 @@ impl Name {
     }
 }
 
+impl From<&str> for Name {
+    fn from(value: &str) -> Self {
+        Self {
+            r#type: "CName".to_string(),
+            storage: "string".to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum Storage {
@@ -67,9 +119,24 @@ impl Serialize for DepotPath {
         S: serde::Serializer,
     {
         match self {
-            Self::ResourcePath(x) => {
+            Self::ResourcePath(x) if Fidelity::trimmed() => {
                 serializer.serialize_str(x.value.as_path().as_os_str().to_str().unwrap_or_default())
             }
+            Self::ResourcePath(x) => {
+                let mut s = serializer.serialize_struct("ResourcePath", 3)?;
+                s.serialize_field("$type", "ResourcePath")?;
+                s.serialize_field("$storage", &x.storage)?;
+                s.serialize_field("$value", &x.value)?;
+                s.end()
+            }
+        }
+    }
+}
+
+impl DepotPath {
+    pub fn as_path(&self) -> &std::path::Path {
+        match self {
+            Self::ResourcePath(x) => x.value.as_path(),
         }
     }
 }
@@ -86,19 +153,63 @@ pub struct Data<T> {
     pub version: usize,
     pub build_version: usize,
     pub root_chunk: T,
+    #[serde(default, flatten)]
+    pub extras: Extras,
 }
 
+/// WolvenKit version files are written for
+const WOLVENKIT_VERSION: &str = "8.17.1";
+/// WolvenKit JSON schema version files are written for
+const WKIT_JSON_VERSION: &str = "0.0.9";
+/// Cyberpunk 2077 version files are written for
+const GAME_VERSION: usize = 2310;
+/// CR2W version files are written for
+const CR2W_VERSION: usize = 195;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Header {
     wolven_kit_version: String,
     w_kit_json_version: String,
     game_version: usize,
+    #[serde(serialize_with = "serialize_date_time")]
     exported_date_time: chrono::DateTime<chrono::Utc>,
     data_type: String,
     archive_file_name: PathBuf,
 }
 
+/// WolvenKit writes dates with a 100 nanoseconds precision (.NET ticks)
+fn serialize_date_time<S>(
+    value: &chrono::DateTime<chrono::Utc>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if Fidelity::trimmed() {
+        return value.serialize(serializer);
+    }
+    serializer.collect_str(&format_args!(
+        "{}.{:07}Z",
+        value.format("%Y-%m-%dT%H:%M:%S"),
+        value.timestamp_subsec_nanos() / 100
+    ))
+}
+
+impl Header {
+    /// header for a file exported now, as WolvenKit would
+    pub fn new(archive_file_name: impl Into<PathBuf>) -> Self {
+        Self {
+            wolven_kit_version: WOLVENKIT_VERSION.to_string(),
+            w_kit_json_version: WKIT_JSON_VERSION.to_string(),
+            game_version: GAME_VERSION,
+            exported_date_time: chrono::Utc::now(),
+            data_type: "CR2W".to_string(),
+            archive_file_name: archive_file_name.into(),
+        }
+    }
+}
+
 impl std::fmt::Display for Header {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -119,6 +230,18 @@ pub struct File<T> {
 }
 
 impl<T> File<T> {
+    /// wrap a freshly created resource
+    pub fn new(archive_file_name: impl Into<PathBuf>, root_chunk: T) -> Self {
+        Self {
+            header: Header::new(archive_file_name),
+            data: Data {
+                version: CR2W_VERSION,
+                build_version: 0,
+                root_chunk,
+                extras: Extras::default(),
+            },
+        }
+    }
     pub fn resource(self) -> T {
         self.data.root_chunk
     }
@@ -138,7 +261,11 @@ impl Serialize for Vector2 {
     where
         S: serde::Serializer,
     {
-        let mut s = serializer.serialize_struct("Vector2", 2)?;
+        let trimmed = Fidelity::trimmed();
+        let mut s = serializer.serialize_struct("Vector2", if trimmed { 2 } else { 3 })?;
+        if !trimmed {
+            s.serialize_field("$type", "Vector2")?;
+        }
         s.serialize_field("X", &self.x)?;
         s.serialize_field("Y", &self.y)?;
         s.end()
@@ -161,11 +288,20 @@ impl Serialize for HDRColor {
     where
         S: serde::Serializer,
     {
-        let mut s = serializer.serialize_struct("HDRColor", 4)?;
-        s.serialize_field("alpha", &self.alpha)?;
-        s.serialize_field("blue", &self.blue)?;
-        s.serialize_field("green", &self.green)?;
-        s.serialize_field("red", &self.red)?;
+        if Fidelity::trimmed() {
+            let mut s = serializer.serialize_struct("HDRColor", 4)?;
+            s.serialize_field("alpha", &self.alpha)?;
+            s.serialize_field("blue", &self.blue)?;
+            s.serialize_field("green", &self.green)?;
+            s.serialize_field("red", &self.red)?;
+            return s.end();
+        }
+        let mut s = serializer.serialize_struct("HDRColor", 5)?;
+        s.serialize_field("$type", "HDRColor")?;
+        s.serialize_field("Alpha", &self.alpha)?;
+        s.serialize_field("Blue", &self.blue)?;
+        s.serialize_field("Green", &self.green)?;
+        s.serialize_field("Red", &self.red)?;
         s.end()
     }
 }
@@ -173,10 +309,29 @@ impl Serialize for HDRColor {
 /// asset handle ID
 ///
 /// identifies the index in the graph.
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq)]
 #[serde(transparent)]
 pub struct HandleId(#[serde(deserialize_with = "deserialize_number_from_string")] u32);
 
+impl Serialize for HandleId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if Fidelity::trimmed() {
+            serializer.serialize_u32(self.0)
+        } else {
+            serializer.collect_str(&self.0)
+        }
+    }
+}
+
+impl From<u32> for HandleId {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
 /// wrapper with handle ID
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -193,7 +348,13 @@ where
     where
         S: serde::Serializer,
     {
-        self.data.serialize(serializer)
+        if Fidelity::trimmed() {
+            return self.data.serialize(serializer);
+        }
+        let mut s = serializer.serialize_struct("InkWrapper", 2)?;
+        s.serialize_field("HandleId", &self.handle_id)?;
+        s.serialize_field("Data", &self.data)?;
+        s.end()
     }
 }
 
@@ -240,12 +401,22 @@ impl Serialize for LocKey {
         S: serde::Serializer,
     {
         match self {
-            Self::ID(x) => serializer.serialize_u32(*x),
+            Self::ID(x) if Fidelity::trimmed() => serializer.serialize_u32(*x),
+            Self::ID(x) => serializer.collect_str(&format_args!("LocKey#{x:05}")),
             Self::Value(x) => serializer.serialize_str(x.as_str()),
         }
     }
 }
 
+impl std::fmt::Display for LocKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ID(x) => write!(f, "{x}"),
+            Self::Value(x) => write!(f, "{x}"),
+        }
+    }
+}
+
 impl PartialEq for LocKey {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -262,6 +433,17 @@ impl PartialEq for LocKey {
 pub struct LocalizationString {
     #[serde(deserialize_with = "deserialize_lockey_from_anything")]
     value: Option<LocKey>,
+    #[serde(default, flatten)]
+    extras: Extras,
+}
+
+impl LocalizationString {
+    /// translation key, if any
+    pub fn key(&self) -> Option<&LocKey> {
+        self.value
+            .as_ref()
+            .filter(|x| **x != LocKey::ID(0) && **x != LocKey::Value("".into()))
+    }
 }
 
 impl Serialize for LocalizationString {
@@ -269,6 +451,17 @@ impl Serialize for LocalizationString {
     where
         S: serde::Serializer,
     {
+        if !Fidelity::trimmed() {
+            let mut s = serializer.serialize_map(None)?;
+            for (key, value) in self.extras.0.iter() {
+                s.serialize_entry(key, value)?;
+            }
+            match &self.value {
+                Some(x) => s.serialize_entry("value", x)?,
+                None => s.serialize_entry("value", "")?,
+            }
+            return s.end();
+        }
         match &self.value {
             Some(x)
                 if *x != LocKey::default()
@@ -283,8 +476,11 @@ impl Serialize for LocalizationString {
 }
 
 pub fn is_any_default_localization_string(
-    LocalizationString { value }: &LocalizationString,
+    LocalizationString { value, .. }: &LocalizationString,
 ) -> bool {
+    if !Fidelity::trimmed() {
+        return false;
+    }
     match value {
         None => true,
         Some(LocKey::ID(0)) => true,
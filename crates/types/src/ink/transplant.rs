@@ -0,0 +1,158 @@
+//! Copying a widget subtree from one .inkwidget to another,
+//! alongside the animations targeting it.
+
+use serde_json::json;
+
+use crate::{
+    Fidelity, HandleAllocator, HandleId, InkWrapper,
+    anim::{InkAnimAnimationLibraryResource, SequenceError, Target},
+    widget::{EditError, PathRemap, SiblingOrNested, Walk, Widget, inkWidgetLibraryResource},
+};
+
+impl inkWidgetLibraryResource {
+    /// widget at `path`, alongside its descendants
+    pub fn widget_at(&self, path: &[usize]) -> Option<&InkWrapper<Widget>> {
+        self.root_chunk()
+            .walk()
+            .into_iter()
+            .find(|x| x.indexes == path)
+            .map(|x| x.widget)
+    }
+
+    fn handle_id_at(&self, path: &[usize]) -> Result<HandleId, EditError> {
+        if path.is_empty() {
+            return Ok(self.root_chunk().root_widget.handle_id);
+        }
+        self.widget_at(path)
+            .map(|x| x.handle_id)
+            .ok_or_else(|| EditError::NotFound(path.to_vec()))
+    }
+
+    /// copy `widget` (typically from another file) under `parent`, at `index` (last by default)
+    ///
+    /// the copy gets fresh handle IDs and points back to its new parent:
+    /// returns its path alongside how other paths changed.
+    pub fn graft(
+        &mut self,
+        parent: &[usize],
+        index: Option<usize>,
+        widget: &InkWrapper<Widget>,
+    ) -> Result<(Vec<usize>, PathRemap), EditError> {
+        let parent_id = self.handle_id_at(parent)?;
+        let index = match index {
+            Some(index) => index,
+            None => self.root_chunk_mut().children_at_mut(parent)?.len(),
+        };
+        // renumbered first: the parent handle ID could also be declared in the copy
+        let widget = HandleAllocator::over(self).adopt(widget.clone());
+        let mut value = Fidelity::WolvenKit
+            .scope(|| serde_json::to_value(widget))
+            .expect("serialize to JSON");
+        // the only handle ref pointing outside of the subtree
+        value["Data"]["parentWidget"] = Fidelity::WolvenKit.scope(|| {
+            json!({
+                "HandleRefId": parent_id,
+            })
+        });
+        let widget: InkWrapper<Widget> =
+            serde_json::from_value(value).expect("deserialize from JSON");
+        let remap = self.insert(parent, index, widget)?;
+        let mut path = parent.to_vec();
+        path.push(index);
+        Ok((path, remap))
+    }
+}
+
+impl InkAnimAnimationLibraryResource {
+    /// copy every definition of `source` targeting `from` or below, retargeted to `to` or below
+    ///
+    /// definitions land in the sequence with the same name, which must already exist:
+    /// returns the number of definitions copied, for each sequence name.
+    pub fn transplant(
+        &mut self,
+        source: &Self,
+        from: &[usize],
+        to: &[usize],
+    ) -> Result<Vec<(String, usize)>, SequenceError> {
+        let mut handles = HandleAllocator::over(self);
+        let mut copies = vec![];
+        for sequence in source.sequences.iter() {
+            let pairs = sequence
+                .data
+                .definitions
+                .iter()
+                .zip(sequence.data.targets.iter())
+                .filter_map(|(definition, target)| match target {
+                    Target::WithHandleId(handle) if handle.data.path.sibling_or_nested(from) => {
+                        let mut handle = handle.clone();
+                        handle.data.path = to
+                            .iter()
+                            .chain(handle.data.path[from.len()..].iter())
+                            .cloned()
+                            .collect();
+                        Some(handles.adopt((definition.clone(), Target::WithHandleId(handle))))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if pairs.is_empty() {
+                continue;
+            }
+            // sequences mirror library items: never guess nor create one
+            let position = self
+                .sequences
+                .iter()
+                .position(|x| x.name() == sequence.name())
+                .ok_or_else(|| SequenceError::NotFound(sequence.name().to_string()))?;
+            copies.push((position, pairs));
+        }
+        let mut copied = vec![];
+        for (position, pairs) in copies {
+            let destination = &mut self.sequences[position];
+            copied.push((destination.name().to_string(), pairs.len()));
+            for (definition, target) in pairs {
+                destination.data.definitions.push(definition);
+                destination.data.targets.push(target);
+            }
+        }
+        Ok(copied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{File, ink::handle::parse};
+
+    fn fixture() -> inkWidgetLibraryResource {
+        serde_json::from_str::<File<inkWidgetLibraryResource>>(include_str!(
+            "../../../../inkwidget_biomoncheck.json"
+        ))
+        .unwrap()
+        .resource()
+    }
+
+    /// grafting a subtree into itself, where the parent handle ID is declared in the copy
+    #[test]
+    fn graft_points_back_to_parent() {
+        let source = fixture();
+        // Booting_Canvas, and its Booting_H_Panel
+        for parent in [vec![0, 0], vec![0, 0, 4]] {
+            let mut widget = fixture();
+            let parent_id = widget.handle_id_at(&parent).unwrap();
+            let (path, _) = widget
+                .graft(&parent, None, source.widget_at(&[0, 0]).unwrap())
+                .unwrap();
+            let grafted = widget.widget_at(&path).unwrap();
+            assert_ne!(grafted.handle_id, parent_id);
+            let value = Fidelity::WolvenKit
+                .scope(|| serde_json::to_value(grafted))
+                .unwrap();
+            assert_eq!(
+                parse(&value["Data"]["parentWidget"]["HandleRefId"]),
+                parse(&serde_json::to_value(parent_id).unwrap()),
+                "grafted under {parent:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,449 @@
+//! Editing the widget tree in place.
+//!
+//! Inserting, removing or moving a widget shifts its siblings indexes,
+//! which every [target](crate::anim::InkAnimSequenceTargetInfo) path below
+//! their parent depends upon: each edit returns a [PathRemap] to keep them in sync.
+
+use crate::{InkWrapper, Name};
+
+use super::{InkChildren, Walk, Widget, inkWidgetLibraryItemInstance, inkWidgetLibraryResource};
+
+/// placeholder parent for a widget detached from the tree, while being moved
+const DETACHED: usize = usize::MAX;
+
+/// single prefix substitution: `None` when the widget was removed
+type Rule = (Vec<usize>, Option<Vec<usize>>);
+
+/// full path names of a renamed widget (or descendant), before and after
+pub type Renamed = (Vec<String>, Vec<String>);
+
+/// how widget path indexes changed after one or more edits
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathRemap {
+    /// applied one after the other, at most one rule matches per step
+    steps: Vec<Vec<Rule>>,
+}
+
+impl PathRemap {
+    fn step(rules: Vec<Rule>) -> Self {
+        Self { steps: vec![rules] }
+    }
+    /// where a widget path ends up, or `None` if the widget was removed
+    ///
+    /// paths unaffected by the edits are returned as-is.
+    pub fn apply(&self, path: &[usize]) -> Option<Vec<usize>> {
+        let mut path = path.to_vec();
+        for rules in self.steps.iter() {
+            if let Some((from, to)) = rules.iter().find(|(from, _)| path.starts_with(from)) {
+                let to = to.as_ref()?;
+                path = to
+                    .iter()
+                    .chain(path[from.len()..].iter())
+                    .cloned()
+                    .collect();
+            }
+        }
+        Some(path)
+    }
+    /// chain with a subsequent edit
+    pub fn then(mut self, next: PathRemap) -> Self {
+        self.steps.extend(next.steps);
+        self
+    }
+    /// whether no path changed at all
+    pub fn is_identity(&self) -> bool {
+        self.steps.iter().all(|x| x.is_empty())
+    }
+}
+
+/// shift siblings at `from..until` under `parent` by `offset`
+fn shift(parent: &[usize], from: usize, until: usize, offset: isize) -> Vec<Rule> {
+    (from..until)
+        .map(|idx| {
+            let mut before = parent.to_vec();
+            before.push(idx);
+            let mut after = parent.to_vec();
+            after.push(idx.checked_add_signed(offset).expect("valid sibling index"));
+            (before, Some(after))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditError {
+    /// no widget at this path
+    NotFound(Vec<usize>),
+    /// widget at this path cannot have children
+    NotCompound(Vec<usize>),
+    /// index past the children count of the parent at this path
+    OutOfBounds { parent: Vec<usize>, index: usize },
+    /// new order is not a permutation of the current children indexes
+    InvalidOrder(Vec<usize>),
+    /// widget cannot be moved below itself
+    IntoItself(Vec<usize>),
+    /// a sibling under the parent at this path already has this name
+    NameTaken { parent: Vec<usize>, name: String },
+    /// name is empty or contains a path separator
+    InvalidName(String),
+}
+
+impl std::fmt::Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dotted = |x: &[usize]| {
+            x.iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(".")
+        };
+        match self {
+            Self::NotFound(path) => write!(f, "no widget at {}", dotted(path)),
+            Self::NotCompound(path) => write!(f, "widget at {} has no children", dotted(path)),
+            Self::OutOfBounds { parent, index } => {
+                write!(f, "index {index} out of bounds under {}", dotted(parent))
+            }
+            Self::InvalidOrder(order) => write!(f, "invalid children order {}", dotted(order)),
+            Self::IntoItself(path) => write!(f, "cannot move {} below itself", dotted(path)),
+            Self::NameTaken { parent, name } => {
+                write!(f, "{name} already exists under {}", dotted(parent))
+            }
+            Self::InvalidName(name) => write!(f, "invalid widget name \"{name}\""),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+impl inkWidgetLibraryItemInstance {
+    /// children of the widget at `parent` (the root widget when empty)
+    pub fn children_at_mut(
+        &mut self,
+        parent: &[usize],
+    ) -> Result<&mut Vec<InkWrapper<Widget>>, EditError> {
+        let mut children = self.root_widget.children_mut();
+        for (depth, idx) in parent.iter().enumerate() {
+            let child = children
+                .get_mut(*idx)
+                .ok_or_else(|| EditError::NotFound(parent[..=depth].to_vec()))?;
+            children = child
+                .data
+                .as_compound_mut()
+                .ok_or_else(|| EditError::NotCompound(parent[..=depth].to_vec()))?
+                .children_mut();
+        }
+        Ok(children)
+    }
+}
+
+fn split(path: &[usize]) -> Result<(&[usize], usize), EditError> {
+    path.split_last()
+        .map(|(idx, parent)| (parent, *idx))
+        .ok_or_else(|| EditError::NotFound(path.to_vec()))
+}
+
+impl inkWidgetLibraryResource {
+    /// insert widget as child of `parent`, at `index`
+    pub fn insert(
+        &mut self,
+        parent: &[usize],
+        index: usize,
+        widget: InkWrapper<Widget>,
+    ) -> Result<PathRemap, EditError> {
+        let children = self.root_chunk_mut().children_at_mut(parent)?;
+        let count = children.len();
+        if index > count {
+            return Err(EditError::OutOfBounds {
+                parent: parent.to_vec(),
+                index,
+            });
+        }
+        children.insert(index, widget);
+        Ok(PathRemap::step(shift(parent, index, count, 1)))
+    }
+
+    /// remove widget (alongside its descendants) at `path`
+    pub fn remove(&mut self, path: &[usize]) -> Result<(InkWrapper<Widget>, PathRemap), EditError> {
+        let (parent, index) = split(path)?;
+        let children = self.root_chunk_mut().children_at_mut(parent)?;
+        let count = children.len();
+        if index >= count {
+            return Err(EditError::NotFound(path.to_vec()));
+        }
+        let removed = children.remove(index);
+        let mut rules = vec![(path.to_vec(), None)];
+        rules.extend(shift(parent, index + 1, count, -1));
+        Ok((removed, PathRemap::step(rules)))
+    }
+
+    /// move widget at `from` under `parent`, at `index`
+    ///
+    /// both `parent` and `index` are expressed as in the tree *before* the move.
+    pub fn move_widget(
+        &mut self,
+        from: &[usize],
+        parent: &[usize],
+        index: usize,
+    ) -> Result<PathRemap, EditError> {
+        if parent.starts_with(from) {
+            return Err(EditError::IntoItself(from.to_vec()));
+        }
+        // validate destination before detaching anything
+        let count = self.root_chunk_mut().children_at_mut(parent)?.len();
+        if index > count {
+            return Err(EditError::OutOfBounds {
+                parent: parent.to_vec(),
+                index,
+            });
+        }
+        let (widget, removed) = self.remove(from)?;
+        let mut detach = removed.steps.into_iter().next().unwrap_or_default();
+        detach[0].1 = Some(vec![DETACHED]);
+        let detach = PathRemap::step(detach);
+        let destination = detach
+            .apply(parent)
+            .expect("parent is not below moved widget");
+        let (from_parent, from_index) = split(from)?;
+        let index = if from_parent == parent && from_index < index {
+            index - 1
+        } else {
+            index
+        };
+        let count = self.root_chunk_mut().children_at_mut(&destination)?.len();
+        self.root_chunk_mut()
+            .children_at_mut(&destination)?
+            .insert(index, widget);
+        let mut attach = shift(&destination, index, count, 1);
+        let mut target = destination.clone();
+        target.push(index);
+        attach.push((vec![DETACHED], Some(target)));
+        Ok(detach.then(PathRemap::step(attach)))
+    }
+
+    /// reorder children of `parent`
+    ///
+    /// `order` lists current children indexes, in their new order.
+    pub fn reorder(&mut self, parent: &[usize], order: &[usize]) -> Result<PathRemap, EditError> {
+        let children = self.root_chunk_mut().children_at_mut(parent)?;
+        let mut sorted = order.to_vec();
+        sorted.sort_unstable();
+        if sorted != (0..children.len()).collect::<Vec<_>>() {
+            return Err(EditError::InvalidOrder(order.to_vec()));
+        }
+        let mut previous: Vec<Option<InkWrapper<Widget>>> = children.drain(..).map(Some).collect();
+        for old in order {
+            children.push(previous[*old].take().expect("permutation"));
+        }
+        let rules = order
+            .iter()
+            .enumerate()
+            .filter(|(new, old)| new != *old)
+            .map(|(new, old)| {
+                let mut before = parent.to_vec();
+                before.push(*old);
+                let mut after = parent.to_vec();
+                after.push(new);
+                (before, Some(after))
+            })
+            .collect();
+        Ok(PathRemap::step(rules))
+    }
+
+    /// rename widget at `path`
+    ///
+    /// returns full path names of the widget and each of its descendants, before and after.
+    pub fn rename(&mut self, path: &[usize], name: &str) -> Result<Vec<Renamed>, EditError> {
+        if name.is_empty() || name.contains(['.', '/']) {
+            return Err(EditError::InvalidName(name.to_string()));
+        }
+        let (parent, index) = split(path)?;
+        let children = self.root_chunk_mut().children_at_mut(parent)?;
+        if index >= children.len() {
+            return Err(EditError::NotFound(path.to_vec()));
+        }
+        if children
+            .iter()
+            .enumerate()
+            .any(|(idx, x)| idx != index && x.data.name() == Some(name))
+        {
+            return Err(EditError::NameTaken {
+                parent: parent.to_vec(),
+                name: name.to_string(),
+            });
+        }
+        let renamed = |tree: &inkWidgetLibraryItemInstance| {
+            tree.walk()
+                .into_iter()
+                .filter(|x| x.indexes.starts_with(path))
+                .map(|x| x.names)
+                .collect::<Vec<_>>()
+        };
+        let before = renamed(self.root_chunk());
+        let widget = &mut self.root_chunk_mut().children_at_mut(parent)?[index];
+        *widget
+            .data
+            .name_mut()
+            .ok_or_else(|| EditError::NotFound(path.to_vec()))? = Name::from(name);
+        let after = renamed(self.root_chunk());
+        Ok(before.into_iter().zip(after).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{File, widget::WidgetTree};
+
+    fn fixture() -> inkWidgetLibraryResource {
+        serde_json::from_str::<File<inkWidgetLibraryResource>>(include_str!(
+            "../../../../../inkwidget_biomoncheck.json"
+        ))
+        .expect("valid fixture")
+        .resource()
+    }
+
+    /// first compound widget with at least `count` children
+    fn parent_with(widget: &inkWidgetLibraryResource, count: usize) -> Vec<usize> {
+        widget
+            .walk()
+            .into_iter()
+            .find(|x| {
+                x.widget
+                    .data
+                    .as_compound()
+                    .is_some_and(|x| x.children_ref().len() >= count)
+            })
+            .map(|x| x.indexes)
+            .expect("fixture has such a parent")
+    }
+
+    fn widget_at(widget: &inkWidgetLibraryResource, path: &[usize]) -> InkWrapper<Widget> {
+        widget
+            .walk()
+            .into_iter()
+            .find(|x| x.indexes == path)
+            .map(|x| x.widget.clone())
+            .expect("widget at path")
+    }
+
+    fn snapshot(widget: &inkWidgetLibraryResource) -> Vec<(Vec<usize>, Vec<String>)> {
+        widget
+            .walk()
+            .into_iter()
+            .map(|x| (x.indexes, x.names))
+            .collect()
+    }
+
+    /// every widget still in the tree can be found at its remapped path
+    fn assert_remapped(
+        before: &[(Vec<usize>, Vec<String>)],
+        after: &inkWidgetLibraryResource,
+        remap: &PathRemap,
+        removed: Option<&[usize]>,
+    ) {
+        for (indexes, names) in before {
+            let remapped = remap.apply(indexes);
+            if removed.is_some_and(|x| indexes.starts_with(x)) {
+                assert_eq!(remapped, None, "{indexes:?} got removed");
+                continue;
+            }
+            let remapped = remapped.expect("widget still in tree");
+            assert_eq!(
+                after
+                    .get_path_names(&remapped)
+                    .as_ref()
+                    .and_then(|x| x.last()),
+                names.last(),
+                "{indexes:?} remapped to {remapped:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn insert_shifts_next_siblings() {
+        let mut widget = fixture();
+        let parent = parent_with(&widget, 2);
+        let before = snapshot(&widget);
+        let mut first = parent.clone();
+        first.push(0);
+        let inserted = widget_at(&widget, &first);
+        let remap = widget.insert(&parent, 1, inserted).unwrap();
+        let mut second = parent.clone();
+        second.push(1);
+        let mut third = parent.clone();
+        third.push(2);
+        assert_eq!(remap.apply(&first), Some(first.clone()));
+        assert_eq!(remap.apply(&second), Some(third));
+        assert_remapped(&before, &widget, &remap, None);
+    }
+
+    #[test]
+    fn remove_shifts_next_siblings_back() {
+        let mut widget = fixture();
+        let parent = parent_with(&widget, 3);
+        let before = snapshot(&widget);
+        let mut removed = parent.clone();
+        removed.push(0);
+        let mut next = parent.clone();
+        next.push(1);
+        let (_, remap) = widget.remove(&removed).unwrap();
+        assert_eq!(remap.apply(&removed), None);
+        assert_eq!(remap.apply(&next), Some(removed.clone()));
+        assert_remapped(&before, &widget, &remap, Some(&removed));
+    }
+
+    #[test]
+    fn move_within_same_parent() {
+        let mut widget = fixture();
+        let parent = parent_with(&widget, 3);
+        let before = snapshot(&widget);
+        let mut from = parent.clone();
+        from.push(0);
+        let remap = widget.move_widget(&from, &parent, 3).unwrap();
+        let mut to = parent.clone();
+        to.push(2);
+        assert_eq!(remap.apply(&from), Some(to));
+        assert_remapped(&before, &widget, &remap, None);
+    }
+
+    #[test]
+    fn move_to_another_parent() {
+        let mut widget = fixture();
+        let parent = parent_with(&widget, 3);
+        let before = snapshot(&widget);
+        let mut from = parent.clone();
+        from.push(0);
+        let (destination, index) = parent.split_at(parent.len() - 1);
+        let remap = widget.move_widget(&from, destination, index[0]).unwrap();
+        assert_remapped(&before, &widget, &remap, None);
+    }
+
+    #[test]
+    fn reorder_swaps_children() {
+        let mut widget = fixture();
+        let parent = parent_with(&widget, 3);
+        let count = widget_at(&widget, &parent)
+            .data
+            .as_compound()
+            .map(|x| x.children_ref().len())
+            .unwrap();
+        let before = snapshot(&widget);
+        let mut order = (0..count).collect::<Vec<_>>();
+        order.swap(0, 2);
+        let remap = widget.reorder(&parent, &order).unwrap();
+        let mut first = parent.clone();
+        first.push(0);
+        let mut third = parent.clone();
+        third.push(2);
+        assert_eq!(remap.apply(&first), Some(third));
+        assert_remapped(&before, &widget, &remap, None);
+    }
+
+    #[test]
+    fn invalid_order_is_rejected() {
+        let mut widget = fixture();
+        let parent = parent_with(&widget, 3);
+        assert!(matches!(
+            widget.reorder(&parent, &[0, 0]),
+            Err(EditError::InvalidOrder(_))
+        ));
+    }
+}
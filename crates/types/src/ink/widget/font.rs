@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use serde_aux::prelude::deserialize_bool_from_anything;
 
-use crate::{DepotPath, Name};
+use crate::{DepotPath, Extras, Name, serialize_bool};
 
 use super::Flags;
 
@@ -12,6 +13,12 @@ pub struct inkFontFamilyResource {
     flags: Flags,
 }
 
+impl inkFontFamilyResource {
+    pub fn depot_path(&self) -> &DepotPath {
+        &self.depot_path
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Default, Serialize, Clone, Deserialize, PartialEq)]
 pub struct fontStyle(Name);
@@ -54,3 +61,27 @@ pub enum textOverflowPolicy {
     PingPongScroll = 4,
     AdjustToSize = 5,
 }
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Default, Serialize, Clone, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum textWrappingPolicy {
+    #[default]
+    Default = 0,
+    PerCharacter = 1,
+}
+
+/// see [NativeDB](https://nativedb.red4ext.com/textWrappingInfo)
+#[allow(non_camel_case_types)]
+#[derive(Debug, Default, Serialize, Clone, Deserialize, PartialEq)]
+#[serde(tag = "$type", rename_all = "camelCase")]
+pub struct textWrappingInfo {
+    #[serde(
+        deserialize_with = "deserialize_bool_from_anything",
+        serialize_with = "serialize_bool"
+    )]
+    pub auto_wrapping_enabled: bool,
+    pub wrapping_at_position: f32,
+    pub wrapping_policy: textWrappingPolicy,
+    #[serde(default, flatten)]
+    pub extras: Extras,
+}
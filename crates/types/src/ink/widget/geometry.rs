@@ -0,0 +1,453 @@
+//! Approximate on-screen geometry of widgets.
+//!
+//! The game's layout pass is not public: this mimics its anchoring
+//! and panels stacking closely enough to locate widgets on screen,
+//! but text measurement and render transforms nesting are simplified.
+
+use crate::{InkWrapper, Vector2};
+
+use super::{
+    InkChildren, InkWidget, Widget, WidgetNode, inkWidgetLibraryItemInstance,
+    inkWidgetLibraryResource,
+    layout::{inkEAnchor, inkEHorizontalAlign, inkESizeRule, inkEVerticalAlign, inkMargin},
+};
+
+/// axis-aligned rectangle, in root widget pixels
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl BoundingBox {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+    /// smallest box containing both
+    pub fn union(&self, other: &Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Self {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
+    fn inset(&self, margin: &inkMargin) -> Self {
+        Self {
+            x: self.x + margin.left,
+            y: self.y + margin.top,
+            width: (self.width - margin.left - margin.right).max(0.),
+            height: (self.height - margin.top - margin.bottom).max(0.),
+        }
+    }
+}
+
+pub trait Geometry {
+    /// every widget in the tree, in document order,
+    /// alongside its approximate bounding box
+    fn bounding_boxes(&self) -> Vec<(WidgetNode<'_>, BoundingBox)>;
+}
+
+/// normalized anchor position inside the parent, as `(min x, min y, max x, max y)`
+fn anchor_bounds(anchor: &inkEAnchor) -> (f32, f32, f32, f32) {
+    match anchor {
+        inkEAnchor::TopLeft => (0., 0., 0., 0.),
+        inkEAnchor::TopCenter => (0.5, 0., 0.5, 0.),
+        inkEAnchor::TopRight => (1., 0., 1., 0.),
+        inkEAnchor::CenterLeft => (0., 0.5, 0., 0.5),
+        inkEAnchor::Centered => (0.5, 0.5, 0.5, 0.5),
+        inkEAnchor::CenterRight => (1., 0.5, 1., 0.5),
+        inkEAnchor::BottomLeft => (0., 1., 0., 1.),
+        inkEAnchor::BottomCenter => (0.5, 1., 0.5, 1.),
+        inkEAnchor::BottomRight => (1., 1., 1., 1.),
+        inkEAnchor::TopFillHorizontaly => (0., 0., 1., 0.),
+        inkEAnchor::CenterFillHorizontaly => (0., 0.5, 1., 0.5),
+        inkEAnchor::BottomFillHorizontaly => (0., 1., 1., 1.),
+        inkEAnchor::LeftFillVerticaly => (0., 0., 0., 1.),
+        inkEAnchor::CenterFillVerticaly => (0.5, 0., 0.5, 1.),
+        inkEAnchor::RightFillVerticaly => (1., 0., 1., 1.),
+        inkEAnchor::Fill => (0., 0., 1., 1.),
+    }
+}
+
+/// place along a single axis inside a canvas, returning `(position, length)`
+fn anchored(
+    start: f32,
+    length: f32,
+    (min, max): (f32, f32),
+    (before, after): (f32, f32),
+    desired: f32,
+    pivot: f32,
+) -> (f32, f32) {
+    if min != max {
+        let from = start + length * min + before;
+        let to = start + length * max - after;
+        return (from, (to - from).max(0.));
+    }
+    let offset = if min == 0. {
+        before
+    } else if min == 1. {
+        -after
+    } else {
+        before - after
+    };
+    (start + length * min + offset - desired * pivot, desired)
+}
+
+/// align along a single axis inside a panel slot, returning `(position, length)`
+fn aligned(
+    start: f32,
+    length: f32,
+    (before, after): (f32, f32),
+    desired: f32,
+    fill: bool,
+    center: bool,
+    end: bool,
+) -> (f32, f32) {
+    if fill {
+        (start + before, (length - before - after).max(0.))
+    } else if center {
+        (start + (length - desired) / 2. + before - after, desired)
+    } else if end {
+        (start + length - after - desired, desired)
+    } else {
+        (start + before, desired)
+    }
+}
+
+fn child_margin(widget: &Widget) -> inkMargin {
+    match widget {
+        Widget::inkCanvasWidget(x) => x.child_margin.clone(),
+        Widget::inkHorizontalPanelWidget(x) => x.child_margin.clone(),
+        Widget::inkVerticalPanelWidget(x) => x.child_margin.clone(),
+        Widget::inkScrollAreaWidget(x) => x.child_margin.clone(),
+        Widget::inkUniformGridWidget(x) => x.child_margin.clone(),
+        Widget::inkVirtualCompoundWidget(x) => x.child_margin.clone(),
+        Widget::inkFlexWidget(x) => x.child_margin.clone(),
+        Widget::inkCacheWidget(x) => x.child_margin.clone(),
+        _ => inkMargin::default(),
+    }
+}
+
+fn is_backward(widget: &Widget) -> bool {
+    use super::layout::inkEChildOrder::Backward;
+    match widget {
+        Widget::inkHorizontalPanelWidget(x) => x.child_order == Backward,
+        Widget::inkVerticalPanelWidget(x) => x.child_order == Backward,
+        _ => false,
+    }
+}
+
+/// margins around a child in a panel, own margin plus parent's child margin
+fn outer(child: &dyn InkWidget, extra: &inkMargin) -> inkMargin {
+    let own = &child.layout().margin;
+    inkMargin {
+        left: own.left + extra.left,
+        top: own.top + extra.top,
+        right: own.right + extra.right,
+        bottom: own.bottom + extra.bottom,
+    }
+}
+
+/// size a widget wishes to occupy
+fn desired(widget: &Widget) -> Vector2 {
+    let Some(own) = widget.as_widget() else {
+        return Vector2::default();
+    };
+    let Some(compound) = widget.as_compound() else {
+        return own.size().clone();
+    };
+    if !own.fit_to_content() {
+        return own.size().clone();
+    }
+    let extra = child_margin(widget);
+    let mut out = Vector2::default();
+    for child in compound.children_ref() {
+        let Some(inner) = child.data.as_widget() else {
+            continue;
+        };
+        if !inner.visible() {
+            continue;
+        }
+        let size = desired(&child.data);
+        let margin = outer(inner, &extra);
+        let width = size.x + margin.left + margin.right;
+        let height = size.y + margin.top + margin.bottom;
+        match widget {
+            Widget::inkHorizontalPanelWidget(_) => {
+                out.x += width;
+                out.y = out.y.max(height);
+            }
+            Widget::inkVerticalPanelWidget(_) => {
+                out.x = out.x.max(width);
+                out.y += height;
+            }
+            _ => {
+                out.x = out.x.max(width);
+                out.y = out.y.max(height);
+            }
+        }
+    }
+    let padding = &own.layout().padding;
+    out.x += padding.left + padding.right;
+    out.y += padding.top + padding.bottom;
+    out
+}
+
+/// apply render transform (translation, then scale around pivot)
+fn transformed(widget: &Widget, bounds: BoundingBox) -> BoundingBox {
+    let Some(own) = widget.as_widget() else {
+        return bounds;
+    };
+    let pivot = own.render_transform_pivot();
+    let transform = own.render_transform();
+    let scale = &transform.scale.0;
+    let origin_x = bounds.x + bounds.width * pivot.x;
+    let origin_y = bounds.y + bounds.height * pivot.y;
+    BoundingBox {
+        x: origin_x + (bounds.x - origin_x) * scale.x + transform.translation.x,
+        y: origin_y + (bounds.y - origin_y) * scale.y + transform.translation.y,
+        width: bounds.width * scale.x,
+        height: bounds.height * scale.y,
+    }
+}
+
+/// lay out children along the main axis of a panel
+fn stack(
+    parent: &Widget,
+    children: &[InkWrapper<Widget>],
+    content: BoundingBox,
+    horizontal: bool,
+) -> Vec<BoundingBox> {
+    let extra = child_margin(parent);
+    let mut fixed = 0.;
+    let mut coefficients = 0.;
+    for child in children {
+        let Some(inner) = child.data.as_widget() else {
+            continue;
+        };
+        if !inner.visible() {
+            continue;
+        }
+        let margin = outer(inner, &extra);
+        let size = desired(&child.data);
+        if horizontal {
+            fixed += margin.left + margin.right;
+        } else {
+            fixed += margin.top + margin.bottom;
+        }
+        if inner.layout().size_rule == inkESizeRule::Stretch {
+            coefficients += inner.layout().size_coefficient.0;
+        } else if horizontal {
+            fixed += size.x;
+        } else {
+            fixed += size.y;
+        }
+    }
+    let available = if horizontal {
+        content.width
+    } else {
+        content.height
+    };
+    let remaining = (available - fixed).max(0.);
+    let mut out = vec![BoundingBox::default(); children.len()];
+    let mut cursor = if horizontal { content.x } else { content.y };
+    let mut order: Vec<usize> = (0..children.len()).collect();
+    if is_backward(parent) {
+        order.reverse();
+    }
+    for idx in order {
+        let child = &children[idx];
+        let Some(inner) = child.data.as_widget() else {
+            continue;
+        };
+        let margin = outer(inner, &extra);
+        let size = desired(&child.data);
+        let layout = inner.layout();
+        let main = if !inner.visible() {
+            0.
+        } else if layout.size_rule == inkESizeRule::Stretch && coefficients > 0. {
+            remaining * layout.size_coefficient.0 / coefficients
+        } else if horizontal {
+            size.x
+        } else {
+            size.y
+        };
+        out[idx] = if horizontal {
+            let (y, height) = aligned(
+                content.y,
+                content.height,
+                (margin.top, margin.bottom),
+                size.y,
+                layout.v_align == inkEVerticalAlign::Fill,
+                layout.v_align == inkEVerticalAlign::Center,
+                layout.v_align == inkEVerticalAlign::Bottom,
+            );
+            BoundingBox::new(cursor + margin.left, y, main, height)
+        } else {
+            let (x, width) = aligned(
+                content.x,
+                content.width,
+                (margin.left, margin.right),
+                size.x,
+                layout.h_align == inkEHorizontalAlign::Fill,
+                layout.h_align == inkEHorizontalAlign::Center,
+                layout.h_align == inkEHorizontalAlign::Right,
+            );
+            BoundingBox::new(x, cursor + margin.top, width, main)
+        };
+        if inner.visible() {
+            cursor += if horizontal {
+                margin.left + main + margin.right
+            } else {
+                margin.top + main + margin.bottom
+            };
+        }
+    }
+    out
+}
+
+/// lay out children on top of each other, each aligned inside the parent
+fn overlay(
+    parent: &Widget,
+    children: &[InkWrapper<Widget>],
+    content: BoundingBox,
+) -> Vec<BoundingBox> {
+    let extra = child_margin(parent);
+    children
+        .iter()
+        .map(|child| {
+            let Some(inner) = child.data.as_widget() else {
+                return content;
+            };
+            let margin = outer(inner, &extra);
+            let size = desired(&child.data);
+            let layout = inner.layout();
+            let (x, width) = aligned(
+                content.x,
+                content.width,
+                (margin.left, margin.right),
+                size.x,
+                layout.h_align == inkEHorizontalAlign::Fill,
+                layout.h_align == inkEHorizontalAlign::Center,
+                layout.h_align == inkEHorizontalAlign::Right,
+            );
+            let (y, height) = aligned(
+                content.y,
+                content.height,
+                (margin.top, margin.bottom),
+                size.y,
+                layout.v_align == inkEVerticalAlign::Fill,
+                layout.v_align == inkEVerticalAlign::Center,
+                layout.v_align == inkEVerticalAlign::Bottom,
+            );
+            BoundingBox::new(x, y, width, height)
+        })
+        .collect()
+}
+
+/// lay out children freely, each relative to its anchor
+fn canvas(children: &[InkWrapper<Widget>], content: BoundingBox) -> Vec<BoundingBox> {
+    children
+        .iter()
+        .map(|child| {
+            let Some(inner) = child.data.as_widget() else {
+                return content;
+            };
+            let layout = inner.layout();
+            let margin = &layout.margin;
+            let size = desired(&child.data);
+            let (min_x, min_y, max_x, max_y) = anchor_bounds(&layout.anchor);
+            let (x, width) = anchored(
+                content.x,
+                content.width,
+                (min_x, max_x),
+                (margin.left, margin.right),
+                size.x,
+                layout.anchor_point.x,
+            );
+            let (y, height) = anchored(
+                content.y,
+                content.height,
+                (min_y, max_y),
+                (margin.top, margin.bottom),
+                size.y,
+                layout.anchor_point.y,
+            );
+            BoundingBox::new(x, y, width, height)
+        })
+        .collect()
+}
+
+/// content area of a widget once laid out, together with its children slots
+fn arrange(widget: &Widget, bounds: BoundingBox) -> Vec<BoundingBox> {
+    let (Some(own), Some(compound)) = (widget.as_widget(), widget.as_compound()) else {
+        return vec![];
+    };
+    let content = bounds.inset(&own.layout().padding);
+    let children = compound.children_ref();
+    match widget {
+        Widget::inkHorizontalPanelWidget(_) => stack(widget, children, content, true),
+        Widget::inkVerticalPanelWidget(_) | Widget::inkScrollAreaWidget(_) => {
+            stack(widget, children, content, false)
+        }
+        Widget::inkFlexWidget(_) | Widget::inkUniformGridWidget(_) => {
+            overlay(widget, children, content)
+        }
+        _ => canvas(children, content),
+    }
+}
+
+impl Geometry for inkWidgetLibraryItemInstance {
+    fn bounding_boxes(&self) -> Vec<(WidgetNode<'_>, BoundingBox)> {
+        fn visit<'a>(
+            children: &'a [InkWrapper<Widget>],
+            slots: Vec<BoundingBox>,
+            indexes: &[usize],
+            names: &[String],
+            out: &mut Vec<(WidgetNode<'a>, BoundingBox)>,
+        ) {
+            for ((idx, child), slot) in children.iter().enumerate().zip(slots) {
+                let mut indexes = indexes.to_vec();
+                indexes.push(idx);
+                let mut names = names.to_vec();
+                names.push(child.data.name().unwrap_or_default().to_string());
+                let bounds = transformed(&child.data, slot);
+                out.push((
+                    WidgetNode {
+                        indexes: indexes.clone(),
+                        names: names.clone(),
+                        widget: child,
+                    },
+                    bounds,
+                ));
+                if let Some(compound) = child.data.as_compound() {
+                    let slots = arrange(&child.data, bounds);
+                    visit(compound.children_ref(), slots, &indexes, &names, out);
+                }
+            }
+        }
+        let root = &self.root_widget.data;
+        let bounds = BoundingBox::new(0., 0., root.size.x, root.size.y);
+        let children = root.children.data.children_ref();
+        let slots = canvas(children, bounds.inset(&root.layout.padding));
+        let mut out = vec![];
+        visit(children, slots, &[], &[], &mut out);
+        out
+    }
+}
+
+impl Geometry for inkWidgetLibraryResource {
+    fn bounding_boxes(&self) -> Vec<(WidgetNode<'_>, BoundingBox)> {
+        self.root_chunk().bounding_boxes()
+    }
+}
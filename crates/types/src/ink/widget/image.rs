@@ -12,6 +12,12 @@ pub struct inkTextureAtlas {
     flags: Flags,
 }
 
+impl inkTextureAtlas {
+    pub fn depot_path(&self) -> &DepotPath {
+        &self.depot_path
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum inkBrushMirrorType {
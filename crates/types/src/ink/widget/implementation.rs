@@ -2,15 +2,18 @@ use std::fmt::Debug;
 
 use enum_dispatch::enum_dispatch;
 
-use crate::{Name, ink::InkWrapper};
+use crate::{Name, Vector2, ink::InkWrapper};
 
 use super::{
-    SiblingOrNested, Widget, WidgetSummary, inkBorderWidget, inkCacheWidget, inkCanvasWidget,
-    inkCircleWidget, inkFlexWidget, inkHorizontalPanelWidget, inkImageWidget, inkMaskWidget,
-    inkMultiChildren, inkRectangleWidget, inkScrollAreaWidget, inkShapeWidget, inkTextWidget,
-    inkUniformGridWidget, inkVectorGraphicWidget, inkVerticalPanelWidget, inkVideoWidget,
-    inkVirtualCompoundWidget, inkWidgetLibraryItem, inkWidgetLibraryItemInstance,
+    SiblingOrNested, Widget, WidgetNode, WidgetSummary, inkBorderWidget, inkCacheWidget,
+    inkCanvasWidget, inkCircleWidget, inkFlexWidget, inkHorizontalPanelWidget, inkImageWidget,
+    inkMaskWidget, inkMultiChildren, inkRectangleWidget, inkScrollAreaWidget, inkShapeWidget,
+    inkTextWidget, inkUniformGridWidget, inkVectorGraphicWidget, inkVerticalPanelWidget,
+    inkVideoWidget, inkVirtualCompoundWidget, inkWidgetLibraryItem, inkWidgetLibraryItemInstance,
     inkWidgetLibraryResource,
+    layout::{inkUITransform, inkWidgetLayout},
+    occurrence,
+    properties::PropertyManager,
 };
 
 impl SiblingOrNested for Vec<usize> {
@@ -43,6 +46,14 @@ macro_rules! impl_ink_children {
             fn children(&self) -> Vec<InkWrapper<Widget>> {
                 self.children.data.children()
             }
+
+            fn children_ref(&self) -> &[InkWrapper<Widget>] {
+                self.children.data.children_ref()
+            }
+
+            fn children_mut(&mut self) -> &mut Vec<InkWrapper<Widget>> {
+                self.children.data.children_mut()
+            }
         }
     };
 }
@@ -53,6 +64,38 @@ macro_rules! impl_ink_widget {
             fn name(&self) -> &str {
                 self.name.as_str()
             }
+
+            fn layout(&self) -> &inkWidgetLayout {
+                &self.layout
+            }
+
+            fn size(&self) -> &Vector2 {
+                &self.size
+            }
+
+            fn render_transform(&self) -> &inkUITransform {
+                &self.render_transform
+            }
+
+            fn render_transform_pivot(&self) -> &Vector2 {
+                &self.render_transform_pivot.0
+            }
+
+            fn fit_to_content(&self) -> bool {
+                self.fit_to_content
+            }
+
+            fn visible(&self) -> bool {
+                self.visible.0
+            }
+
+            fn opacity(&self) -> f32 {
+                self.opacity.0
+            }
+
+            fn property_manager(&self) -> Option<&PropertyManager> {
+                self.property_manager.as_ref()
+            }
         }
     };
 }
@@ -80,6 +123,15 @@ pub trait Classname {
 
 pub trait InkWidget: Debug {
     fn name(&self) -> &str;
+    fn layout(&self) -> &inkWidgetLayout;
+    fn size(&self) -> &Vector2;
+    fn render_transform(&self) -> &inkUITransform;
+    fn render_transform_pivot(&self) -> &Vector2;
+    fn fit_to_content(&self) -> bool;
+    fn visible(&self) -> bool;
+    fn opacity(&self) -> f32;
+    /// style bindings
+    fn property_manager(&self) -> Option<&PropertyManager>;
 }
 
 /// containers which can contain multiple widgets.
@@ -91,6 +143,12 @@ pub trait InkChildren {
     /// children [wrappers](InkWrapper),
     /// which conserve their relative index in the graph.
     fn children(&self) -> Vec<InkWrapper<Widget>>;
+    /// equivalent to `.children()`
+    /// but borrowed in place instead of cloning the whole subtree.
+    fn children_ref(&self) -> &[InkWrapper<Widget>];
+    /// equivalent to `.children_ref()`
+    /// but mutable, to edit the tree in place.
+    fn children_mut(&mut self) -> &mut Vec<InkWrapper<Widget>>;
 }
 
 pub trait InkLeafWidget: InkWidget + Debug {}
@@ -107,6 +165,14 @@ impl InkChildren for inkMultiChildren {
     fn children(&self) -> Vec<InkWrapper<Widget>> {
         self.children.to_vec()
     }
+
+    fn children_ref(&self) -> &[InkWrapper<Widget>] {
+        self.children.as_slice()
+    }
+
+    fn children_mut(&mut self) -> &mut Vec<InkWrapper<Widget>> {
+        &mut self.children
+    }
 }
 
 impl_ink_children!(inkCanvasWidget);
@@ -190,6 +256,28 @@ impl Widget {
             Self::inkVectorGraphicWidget(node) => Some(node.name()),
         }
     }
+    pub fn name_mut(&mut self) -> Option<&mut Name> {
+        match self {
+            Self::inkMultiChildren(_) => None,
+            Self::inkCanvasWidget(node) => Some(&mut node.name),
+            Self::inkHorizontalPanelWidget(node) => Some(&mut node.name),
+            Self::inkVerticalPanelWidget(node) => Some(&mut node.name),
+            Self::inkScrollAreaWidget(node) => Some(&mut node.name),
+            Self::inkUniformGridWidget(node) => Some(&mut node.name),
+            Self::inkVirtualCompoundWidget(node) => Some(&mut node.name),
+            Self::inkFlexWidget(node) => Some(&mut node.name),
+            Self::inkCacheWidget(node) => Some(&mut node.name),
+            Self::inkTextWidget(node) => Some(&mut node.name),
+            Self::inkImageWidget(node) => Some(&mut node.name),
+            Self::inkVideoWidget(node) => Some(&mut node.name),
+            Self::inkMaskWidget(node) => Some(&mut node.name),
+            Self::inkBorderWidget(node) => Some(&mut node.name),
+            Self::inkShapeWidget(node) => Some(&mut node.name),
+            Self::inkCircleWidget(node) => Some(&mut node.name),
+            Self::inkRectangleWidget(node) => Some(&mut node.name),
+            Self::inkVectorGraphicWidget(node) => Some(&mut node.name),
+        }
+    }
     pub fn as_compound(&self) -> Option<&dyn InkCompoundWidget> {
         match self {
             Self::inkCanvasWidget(node) => Some(node),
@@ -203,6 +291,19 @@ impl Widget {
             _ => None,
         }
     }
+    pub fn as_compound_mut(&mut self) -> Option<&mut dyn InkCompoundWidget> {
+        match self {
+            Self::inkCanvasWidget(node) => Some(node),
+            Self::inkHorizontalPanelWidget(node) => Some(node),
+            Self::inkVerticalPanelWidget(node) => Some(node),
+            Self::inkScrollAreaWidget(node) => Some(node),
+            Self::inkUniformGridWidget(node) => Some(node),
+            Self::inkVirtualCompoundWidget(node) => Some(node),
+            Self::inkFlexWidget(node) => Some(node),
+            Self::inkCacheWidget(node) => Some(node),
+            _ => None,
+        }
+    }
     pub fn as_widget(&self) -> Option<&dyn InkWidget> {
         match self {
             Self::inkMultiChildren(_) => None,
@@ -260,13 +361,20 @@ pub trait WidgetTree {
     fn get_partial_path_indexes(&self, path: &[&str]) -> (Vec<usize>, String);
 }
 
+pub trait Walk {
+    /// every widget in the tree, in document order
+    /// (each parent immediately followed by its descendants)
+    fn walk(&self) -> Vec<WidgetNode<'_>>;
+}
+
 pub trait ByIndex {
     /// find a widget by index
     fn by_index(&self, idx: usize) -> Option<Widget>;
 }
 
 pub trait ByName {
-    /// find a widget by name
+    /// find a widget by name, optionally suffixed with its occurrence among siblings
+    /// (e.g. `warning_Flex1[2]`), the first one otherwise
     fn by_name(&self, name: &str) -> Option<(usize, Widget)>;
 }
 
@@ -286,6 +394,14 @@ where
     fn children(&self) -> Vec<InkWrapper<Widget>> {
         self.data.children()
     }
+
+    fn children_ref(&self) -> &[InkWrapper<Widget>] {
+        self.data.children_ref()
+    }
+
+    fn children_mut(&mut self) -> &mut Vec<InkWrapper<Widget>> {
+        self.data.children_mut()
+    }
 }
 
 impl InkChildren for inkWidgetLibraryItem {
@@ -296,6 +412,14 @@ impl InkChildren for inkWidgetLibraryItem {
     fn children(&self) -> Vec<InkWrapper<Widget>> {
         self.package.data.file.root_chunk.root_widget.children()
     }
+
+    fn children_ref(&self) -> &[InkWrapper<Widget>] {
+        self.package.data.file.root_chunk.root_widget.children_ref()
+    }
+
+    fn children_mut(&mut self) -> &mut Vec<InkWrapper<Widget>> {
+        self.package.data.file.root_chunk.root_widget.children_mut()
+    }
 }
 
 impl<T> ByIndex for T
@@ -312,6 +436,8 @@ where
     T: InkChildren,
 {
     fn by_name(&self, name: &str) -> Option<(usize, Widget)> {
+        let (name, nth) = occurrence(name);
+        let mut nth = nth.unwrap_or(1);
         for (idx, child) in self.orphans().iter().enumerate() {
             if let Widget::inkMultiChildren(_) = &child {
                 panic!("unexpected inkMultiChildren with name {name}");
@@ -319,7 +445,10 @@ where
             if let Some(compound) = child.as_compound()
                 && compound.name() == name
             {
-                return Some((idx, child.clone()));
+                nth -= 1;
+                if nth == 0 {
+                    return Some((idx, child.clone()));
+                }
             }
             continue;
         }
@@ -351,19 +480,15 @@ where
 
 impl ByName for Vec<InkWrapper<Widget>> {
     fn by_name(&self, name: &str) -> Option<(usize, Widget)> {
-        for (idx, widget) in self.iter().enumerate() {
-            if let Some(compound) = widget.data.as_compound()
-                && compound.name() == name
-            {
-                return Some((idx, widget.data.clone()));
-            }
-            if let Some(leaf) = widget.data.as_leaf()
-                && leaf.name() == name
-            {
-                return Some((idx, widget.data.clone()));
-            }
-        }
-        None
+        let (name, nth) = occurrence(name);
+        self.iter()
+            .enumerate()
+            .filter(|(_, widget)| {
+                widget.data.as_compound().is_some_and(|x| x.name() == name)
+                    || widget.data.as_leaf().is_some_and(|x| x.name() == name)
+            })
+            .nth(nth.unwrap_or(1) - 1)
+            .map(|(idx, widget)| (idx, widget.data.clone()))
     }
 }
 
@@ -494,11 +619,41 @@ impl WidgetTree for inkWidgetLibraryItemInstance {
                 parent = Some(widget);
                 continue;
             }
+            return (indexes, name.to_string());
         }
         (indexes, path.last().unwrap().to_string())
     }
 }
 
+impl Walk for inkWidgetLibraryItemInstance {
+    fn walk(&self) -> Vec<WidgetNode<'_>> {
+        fn visit<'a>(
+            children: &'a [InkWrapper<Widget>],
+            indexes: &[usize],
+            names: &[String],
+            out: &mut Vec<WidgetNode<'a>>,
+        ) {
+            for (idx, child) in children.iter().enumerate() {
+                let mut indexes = indexes.to_vec();
+                indexes.push(idx);
+                let mut names = names.to_vec();
+                names.push(child.data.name().unwrap_or_default().to_string());
+                out.push(WidgetNode {
+                    indexes: indexes.clone(),
+                    names: names.clone(),
+                    widget: child,
+                });
+                if let Some(compound) = child.data.as_compound() {
+                    visit(compound.children_ref(), &indexes, &names, out);
+                }
+            }
+        }
+        let mut out = vec![];
+        visit(self.root_widget.children_ref(), &[], &[], &mut out);
+        out
+    }
+}
+
 impl inkWidgetLibraryResource {
     pub fn root(&self) -> &inkWidgetLibraryItem {
         self.library_items.first().expect("Root")
@@ -506,6 +661,16 @@ impl inkWidgetLibraryResource {
     pub fn root_chunk(&self) -> &inkWidgetLibraryItemInstance {
         &self.root().package.data.file.root_chunk
     }
+    pub fn root_chunk_mut(&mut self) -> &mut inkWidgetLibraryItemInstance {
+        &mut self
+            .library_items
+            .first_mut()
+            .expect("Root")
+            .package
+            .data
+            .file
+            .root_chunk
+    }
 }
 
 impl WidgetTree for inkWidgetLibraryResource {
@@ -525,3 +690,9 @@ impl WidgetTree for inkWidgetLibraryResource {
         self.root_chunk().get_partial_path_indexes(names)
     }
 }
+
+impl Walk for inkWidgetLibraryResource {
+    fn walk(&self) -> Vec<WidgetNode<'_>> {
+        self.root_chunk().walk()
+    }
+}
@@ -46,6 +46,7 @@ pub enum inkEVerticalAlign {
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "$type")]
 pub struct inkUITransform {
     pub translation: Vector2,
     pub scale: Scale,
@@ -55,7 +56,7 @@ pub struct inkUITransform {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
-pub struct Scale(Vector2);
+pub struct Scale(pub Vector2);
 impl Default for Scale {
     fn default() -> Self {
         Self(Vector2 { x: 1., y: 1. })
@@ -91,6 +92,30 @@ pub struct inkWidgetLayout {
     pub margin: inkMargin,
     #[serde(rename = "HAlign")]
     pub h_align: inkEHorizontalAlign,
+    #[serde(default, rename = "VAlign")]
+    pub v_align: inkEVerticalAlign,
+    #[serde(default)]
+    pub size_rule: inkESizeRule,
+    #[serde(default)]
+    pub size_coefficient: SizeCoefficient,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum inkESizeRule {
+    #[default]
+    Fixed = 0,
+    Stretch = 1,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct SizeCoefficient(pub f32);
+
+impl Default for SizeCoefficient {
+    fn default() -> Self {
+        Self(1.)
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -1,24 +1,39 @@
 //! All widgets in Cyberpunk 2077 UI
 //! are similar to web and traditional UI frameworks.
 
+mod edit;
 pub mod font;
+pub mod geometry;
 pub mod image;
 pub(crate) mod implementation;
 pub mod layout;
+mod names;
 pub mod properties;
+mod search;
+mod selector;
+pub mod text;
 
+pub use edit::*;
 use enum_dispatch::enum_dispatch;
 pub use implementation::*;
+pub use names::*;
+pub use search::*;
+pub use selector::*;
 
-use serde::{Deserialize, Serialize, ser::SerializeSeq};
+use serde::{
+    Deserialize, Serialize,
+    ser::{SerializeSeq, SerializeStruct},
+};
 use serde_aux::prelude::deserialize_bool_from_anything;
 
-use crate::{DepotPath, Name, Vector2, is_any_default_localization_string};
+use crate::{
+    DepotPath, Extras, Fidelity, Name, Vector2, is_any_default_localization_string, serialize_bool,
+};
 
 use self::{
     font::{
         fontStyle, inkFontFamilyResource, textHorizontalAlignment, textLetterCase,
-        textOverflowPolicy, textVerticalAlignment,
+        textOverflowPolicy, textVerticalAlignment, textWrappingInfo,
     },
     image::{inkBrushMirrorType, inkBrushTileType, inkTextureAtlas},
     layout::{inkEHorizontalAlign, inkEVerticalAlign, inkMargin, textJustificationType},
@@ -41,7 +56,7 @@ pub enum Flags {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct Pivot(Vector2);
+pub struct Pivot(pub Vector2);
 
 impl Default for Pivot {
     fn default() -> Self {
@@ -49,8 +64,35 @@ impl Default for Pivot {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Visible(
+    #[serde(
+        deserialize_with = "deserialize_bool_from_anything",
+        serialize_with = "serialize_bool"
+    )]
+    pub bool,
+);
+
+impl Default for Visible {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct Opacity(pub f32);
+
+impl Default for Opacity {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+/// defaults are only omitted from trimmed output
 fn is_default<T: Default + PartialEq>(value: &T) -> bool {
-    *value == T::default()
+    Fidelity::trimmed() && *value == T::default()
 }
 
 macro_rules! native_compound_widget {
@@ -68,6 +110,29 @@ macro_rules! native_compound_widget {
             pub child_order: self::layout::inkEChildOrder,
             #[serde(default, skip_serializing_if = "is_default")]
             pub child_margin: self::layout::inkMargin,
+            #[serde(default, skip_serializing_if = "is_default")]
+            pub layout: self::layout::inkWidgetLayout,
+            #[serde(default, skip_serializing_if = "is_default")]
+            pub property_manager: Option<self::properties::PropertyManager>,
+            #[serde(default, skip_serializing_if = "is_default")]
+            pub render_transform_pivot: self::Pivot,
+            #[serde(default, skip_serializing_if = "is_default")]
+            pub render_transform: self::layout::inkUITransform,
+            #[serde(default, skip_serializing_if = "is_default")]
+            pub size: crate::Vector2,
+            #[serde(
+                default,
+                skip_serializing_if = "is_default",
+                deserialize_with = "deserialize_bool_from_anything",
+                serialize_with = "serialize_bool"
+            )]
+            pub fit_to_content: bool,
+            #[serde(default, skip_serializing_if = "is_default")]
+            pub visible: self::Visible,
+            #[serde(default, skip_serializing_if = "is_default")]
+            pub opacity: self::Opacity,
+            #[serde(default, flatten)]
+            pub extras: $crate::Extras,
         }
     };
 }
@@ -91,7 +156,20 @@ macro_rules! native_leaf_widget {
             pub render_transform: self::layout::inkUITransform,
             #[serde(default, skip_serializing_if = "is_default")]
             pub size: crate::Vector2,
+            #[serde(
+                default,
+                skip_serializing_if = "is_default",
+                deserialize_with = "deserialize_bool_from_anything",
+                serialize_with = "serialize_bool"
+            )]
+            pub fit_to_content: bool,
+            #[serde(default, skip_serializing_if = "is_default")]
+            pub visible: self::Visible,
+            #[serde(default, skip_serializing_if = "is_default")]
+            pub opacity: self::Opacity,
             $($tt)*
+            #[serde(default, flatten)]
+            pub extras: $crate::Extras,
         }
     };
     ($ty:ident) => {
@@ -120,6 +198,12 @@ impl Serialize for inkMultiChildren {
     where
         S: serde::Serializer,
     {
+        if !Fidelity::trimmed() {
+            let mut s = serializer.serialize_struct("inkMultiChildren", 2)?;
+            s.serialize_field("$type", "inkMultiChildren")?;
+            s.serialize_field("children", &self.children)?;
+            return s.end();
+        }
         let mut seq = serializer.serialize_seq(Some(self.children.len()))?;
         for elem in self.children.iter() {
             seq.serialize_element(elem)?;
@@ -159,9 +243,18 @@ native_leaf_widget!(inkTextWidget {
   pub font_style: fontStyle,
   #[serde(default, skip_serializing_if = "is_default")]
   pub justification: textJustificationType,
-  #[serde(default, skip_serializing_if = "is_default")]
+  #[serde(
+      default,
+      skip_serializing_if = "is_default",
+      rename = "letterCase",
+      alias = "textLetterCase"
+  )]
   pub text_letter_case: Option<textLetterCase>,
   #[serde(default, skip_serializing_if = "is_default")]
+  pub font_size: f32,
+  #[serde(default, skip_serializing_if = "is_default")]
+  pub tracking: f32,
+  #[serde(default, skip_serializing_if = "is_default")]
   pub line_height_percentage: f32,
   #[serde(default, skip_serializing_if = "is_default")]
   pub text_horizontal_alignment: textHorizontalAlignment,
@@ -170,6 +263,8 @@ native_leaf_widget!(inkTextWidget {
   #[serde(default, skip_serializing_if = "is_default")]
   pub text_overflow_policy: textOverflowPolicy,
   #[serde(default, skip_serializing_if = "is_default")]
+  pub wrapping_info: textWrappingInfo,
+  #[serde(default, skip_serializing_if = "is_default")]
   pub content_h_align: inkEHorizontalAlign,
   #[serde(default, skip_serializing_if = "is_default")]
   pub content_v_align: inkEVerticalAlign,
@@ -179,10 +274,16 @@ native_leaf_widget!(inkTextWidget {
   pub scroll_text_speed: self::ScrollTextSpeed,
 });
 native_leaf_widget!(inkImageWidget {
-    #[serde(deserialize_with = "deserialize_bool_from_anything")]
+    #[serde(
+        deserialize_with = "deserialize_bool_from_anything",
+        serialize_with = "serialize_bool"
+    )]
     pub use_external_dynamic_texture: bool,
     pub external_dynamic_texture: Name,
-    #[serde(deserialize_with = "deserialize_bool_from_anything")]
+    #[serde(
+        deserialize_with = "deserialize_bool_from_anything",
+        serialize_with = "serialize_bool"
+    )]
     pub use_nine_slice_scale: bool,
     pub nine_slice_scale: inkMargin,
     pub mirror_type: inkBrushMirrorType,
@@ -237,9 +338,48 @@ pub enum Widget {
 /// see [NativeDB](https://nativedb.red4ext.com/inkWidgetLibraryItemInstance)
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(tag = "$type", rename_all = "camelCase")]
 pub struct inkWidgetLibraryItemInstance {
+    #[serde(
+        serialize_with = "serialize_root",
+        deserialize_with = "deserialize_root"
+    )]
     pub root_widget: InkWrapper<inkCanvasWidget>,
+    #[serde(default, flatten)]
+    pub extras: Extras,
+}
+
+/// root widget tagged with its class, like any other widget
+#[allow(non_camel_case_types)]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "$type")]
+enum Root<T> {
+    inkCanvasWidget(T),
+}
+
+fn serialize_root<S>(root: &InkWrapper<inkCanvasWidget>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if Fidelity::trimmed() {
+        return root.serialize(serializer);
+    }
+    InkWrapper {
+        handle_id: root.handle_id,
+        data: Root::inkCanvasWidget(&root.data),
+    }
+    .serialize(serializer)
+}
+
+fn deserialize_root<'de, D>(deserializer: D) -> Result<InkWrapper<inkCanvasWidget>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let InkWrapper {
+        handle_id,
+        data: Root::inkCanvasWidget(data),
+    } = InkWrapper::<Root<inkCanvasWidget>>::deserialize(deserializer)?;
+    Ok(InkWrapper { handle_id, data })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -252,14 +392,19 @@ pub struct Data {
 #[serde(rename_all = "PascalCase")]
 pub struct Package {
     pub data: self::Data,
+    #[serde(default, flatten)]
+    pub extras: Extras,
 }
 
 /// see [NativeDB](https://nativedb.red4ext.com/inkWidgetLibraryItem)
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "$type")]
 pub struct inkWidgetLibraryItem {
     pub name: Name,
     pub package: Package,
+    #[serde(default, flatten)]
+    pub extras: Extras,
 }
 
 /// see [NativeDB](https://nativedb.red4ext.com/inkanimAnimationLibraryResource)
@@ -274,10 +419,12 @@ pub struct inkanimAnimationLibraryResource {
 /// see [NativeDB](https://nativedb.red4ext.com/inkWidgetLibraryResource)
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(tag = "$type", rename_all = "camelCase")]
 pub struct inkWidgetLibraryResource {
     pub animation_library_res_ref: inkanimAnimationLibraryResource,
     pub library_items: Vec<inkWidgetLibraryItem>,
+    #[serde(default, flatten)]
+    pub extras: Extras,
 }
 
 /// widget aggregated informations summary
@@ -289,3 +436,14 @@ pub struct WidgetSummary {
     /// widget name
     pub Name: Name,
 }
+
+/// widget visited while walking the tree, alongside its location
+#[derive(Debug, Clone)]
+pub struct WidgetNode<'a> {
+    /// full path indexes to the widget
+    pub indexes: Vec<usize>,
+    /// full path names to the widget
+    pub names: Vec<String>,
+    /// widget itself, with its handle ID
+    pub widget: &'a InkWrapper<Widget>,
+}
@@ -0,0 +1,349 @@
+//! Sibling widgets sharing the same name, and name paths which fail to resolve.
+//!
+//! Auto-generated names like `inkVerticalPanelWidget7` repeat a lot,
+//! so name paths accept an occurrence suffix, e.g. `warning_Flex1[2]`
+//! for the second sibling named `warning_Flex1`.
+
+use serde::Serialize;
+
+use super::{Walk, WidgetNode, WidgetTree, inkWidgetLibraryResource};
+
+/// split `name[n]` into its name and 1-based occurrence among siblings, if any
+pub fn occurrence(name: &str) -> (&str, Option<usize>) {
+    if let Some(stripped) = name.strip_suffix(']')
+        && let Some((name, nth)) = stripped.rsplit_once('[')
+        && let Ok(nth) = nth.parse::<usize>()
+        && nth > 0
+    {
+        return (name, Some(nth));
+    }
+    (name, None)
+}
+
+/// several siblings sharing the same name
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateName {
+    /// parent path indexes
+    pub parent: Vec<usize>,
+    pub name: String,
+    /// sibling indexes, in document order
+    pub siblings: Vec<usize>,
+}
+
+/// name path which could not be fully resolved
+#[derive(Debug, Clone, Serialize)]
+pub struct Unresolved {
+    /// path indexes of the longest resolved prefix
+    pub resolved: Vec<usize>,
+    /// name which could not be found below the resolved prefix
+    pub missing: String,
+    /// names of the children available below the resolved prefix,
+    /// with occurrence suffix wherever siblings share a name
+    pub available: Vec<String>,
+    /// closest available names, closest first,
+    /// with every existing occurrence suffix wherever siblings share a name
+    pub suggestions: Vec<String>,
+    /// number of children named exactly as searched, when only its occurrence is out of range
+    pub namesakes: usize,
+}
+
+/// edits needed to turn `a` into `b`
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, x) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, y) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(x != *y);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// distance between a searched name and an available one, if close enough:
+/// case differences are free, and a name containing the other is always close
+fn distance(searched: &str, available: &str) -> Option<usize> {
+    let (searched, available) = (searched.to_lowercase(), available.to_lowercase());
+    let distance = levenshtein(&searched, &available);
+    if distance <= (searched.chars().count() / 3).max(2)
+        || available.contains(&searched)
+        || searched.contains(&available)
+    {
+        return Some(distance);
+    }
+    None
+}
+
+fn children_of<'a, 'b>(
+    nodes: &'b [WidgetNode<'a>],
+    parent: &'b [usize],
+) -> impl Iterator<Item = &'b WidgetNode<'a>> {
+    nodes
+        .iter()
+        .filter(move |x| x.indexes.len() == parent.len() + 1 && x.indexes.starts_with(parent))
+}
+
+impl inkWidgetLibraryResource {
+    /// every group of siblings sharing the same name, in document order
+    pub fn duplicate_names(&self) -> Vec<DuplicateName> {
+        let mut duplicates: Vec<DuplicateName> = vec![];
+        for node in self.walk() {
+            let Some((idx, parent)) = node.indexes.split_last() else {
+                continue;
+            };
+            let Some(name) = node.names.last().filter(|x| !x.is_empty()) else {
+                continue;
+            };
+            match duplicates
+                .iter_mut()
+                .find(|x| x.parent == parent && &x.name == name)
+            {
+                Some(group) => group.siblings.push(*idx),
+                None => duplicates.push(DuplicateName {
+                    parent: parent.to_vec(),
+                    name: name.clone(),
+                    siblings: vec![*idx],
+                }),
+            }
+        }
+        duplicates.retain(|x| x.siblings.len() > 1);
+        duplicates
+    }
+
+    /// every full path indexes matching the names
+    ///
+    /// a name without occurrence suffix matches all siblings sharing it
+    pub fn find_path_indexes(&self, path: &[&str]) -> Vec<Vec<usize>> {
+        let nodes = self.walk();
+        let mut candidates: Vec<Vec<usize>> = vec![vec![]];
+        for searched in path {
+            let (name, nth) = occurrence(searched);
+            candidates = candidates
+                .iter()
+                .flat_map(|parent| {
+                    let matching = children_of(&nodes, parent)
+                        .filter(|x| x.names.last().is_some_and(|x| x == name))
+                        .map(|x| x.indexes.clone());
+                    match nth {
+                        Some(nth) => matching.skip(nth - 1).take(1).collect::<Vec<_>>(),
+                        None => matching.collect::<Vec<_>>(),
+                    }
+                })
+                .collect();
+        }
+        candidates
+    }
+
+    /// full path names to the widget, with occurrence suffix wherever siblings share a name
+    pub fn unambiguous_path_names(&self, path: &[usize]) -> Option<Vec<String>> {
+        let nodes = self.walk();
+        let mut names = Vec::with_capacity(path.len());
+        for depth in 0..path.len() {
+            let (parent, current) = (&path[..depth], &path[..=depth]);
+            let node = nodes.iter().find(|x| x.indexes == current)?;
+            let name = node.names.last()?;
+            let namesakes = children_of(&nodes, parent)
+                .filter(|x| x.names.last() == Some(name))
+                .map(|x| x.indexes.last().copied().unwrap_or_default())
+                .collect::<Vec<_>>();
+            if namesakes.len() > 1 {
+                let nth = namesakes.iter().take_while(|x| **x <= path[depth]).count();
+                names.push(format!("{name}[{nth}]"));
+            } else {
+                names.push(name.clone());
+            }
+        }
+        Some(names)
+    }
+
+    /// why the names path fails to resolve, if it does
+    pub fn unresolved(&self, path: &[&str]) -> Option<Unresolved> {
+        let (resolved, missing) = self.get_partial_path_indexes(path);
+        if resolved.len() == path.len() {
+            return None;
+        }
+        let nodes = self.walk();
+        let available = children_of(&nodes, &resolved)
+            .filter_map(|x| self.unambiguous_path_names(&x.indexes))
+            .filter_map(|x| x.last().cloned())
+            .collect::<Vec<_>>();
+        let (searched, nth) = occurrence(&missing);
+        // each distinct name, alongside how many siblings share it
+        let counted = available.iter().map(|x| occurrence(x).0).fold(
+            Vec::<(&str, usize)>::new(),
+            |mut names, name| {
+                match names.iter_mut().find(|(x, _)| *x == name) {
+                    Some((_, count)) => *count += 1,
+                    None => names.push((name, 1)),
+                }
+                names
+            },
+        );
+        let namesakes = counted
+            .iter()
+            .find(|(name, _)| *name == searched)
+            .map(|(_, count)| *count)
+            .unwrap_or_default();
+        let mut scored = counted
+            .into_iter()
+            .filter_map(|(name, count)| distance(searched, name).map(|x| (x, name, count)))
+            .collect::<Vec<_>>();
+        // stable, so ties keep document order
+        scored.sort_by_key(|(distance, _, _)| *distance);
+        let suggestions = scored
+            .into_iter()
+            .flat_map(|(_, name, count)| match (count, nth) {
+                (1, _) => vec![name.to_string()],
+                (count, Some(nth)) if nth <= count => vec![format!("{name}[{nth}]")],
+                (count, _) => (1..=count).map(|nth| format!("{name}[{nth}]")).collect(),
+            })
+            .collect();
+        Some(Unresolved {
+            resolved,
+            missing,
+            available,
+            suggestions,
+            namesakes,
+        })
+    }
+
+    /// names path where each name which fails to resolve is replaced
+    /// by its closest match, as long as a single one is the closest
+    pub fn fuzzy_path_names(&self, path: &[&str]) -> Result<Vec<String>, Unresolved> {
+        let mut names = path.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        loop {
+            let borrowed = names.iter().map(|x| x.as_str()).collect::<Vec<_>>();
+            let Some(unresolved) = self.unresolved(&borrowed) else {
+                return Ok(names);
+            };
+            let (searched, _) = occurrence(&unresolved.missing);
+            let closest = unresolved
+                .suggestions
+                .iter()
+                .map(|x| distance(searched, occurrence(x).0).unwrap_or(usize::MAX))
+                .collect::<Vec<_>>();
+            let unique = match closest.as_slice() {
+                [first, second, ..] => first < second,
+                [_] => true,
+                [] => false,
+            };
+            match unresolved.suggestions.first() {
+                // occurrence out of range, which no correction can fix
+                Some(best) if unique && unresolved.namesakes == 0 => {
+                    names[unresolved.resolved.len()] = best.clone()
+                }
+                _ => return Err(unresolved),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::File;
+
+    const PANEL: [&str; 3] = ["MAIN_CANVAS", "Booting_Canvas", "Booting_H_Panel"];
+
+    fn fixture() -> inkWidgetLibraryResource {
+        serde_json::from_str::<File<inkWidgetLibraryResource>>(include_str!(
+            "../../../../../inkwidget_biomoncheck.json"
+        ))
+        .unwrap()
+        .resource()
+    }
+
+    fn below_panel(name: &str) -> Vec<&str> {
+        PANEL.iter().copied().chain([name]).collect()
+    }
+
+    #[test]
+    fn resolved() {
+        let widget = fixture();
+        assert!(widget.unresolved(&below_panel("BOOTING_dot1[4]")).is_none());
+        assert_eq!(
+            widget
+                .fuzzy_path_names(&below_panel("BOOTING_text"))
+                .unwrap(),
+            below_panel("BOOTING_text")
+        );
+    }
+
+    #[test]
+    fn occurrence_out_of_range() {
+        let widget = fixture();
+        let unresolved = widget.unresolved(&below_panel("BOOTING_dot1[9]")).unwrap();
+        assert_eq!(unresolved.resolved, vec![0, 0, 4]);
+        assert_eq!(unresolved.missing, "BOOTING_dot1[9]");
+        assert_eq!(unresolved.namesakes, 4);
+        assert_eq!(
+            unresolved.suggestions,
+            vec![
+                "BOOTING_dot1[1]",
+                "BOOTING_dot1[2]",
+                "BOOTING_dot1[3]",
+                "BOOTING_dot1[4]",
+                "BOOTING_text"
+            ]
+        );
+        assert!(
+            widget
+                .fuzzy_path_names(&below_panel("BOOTING_dot1[9]"))
+                .is_err()
+        );
+
+        let unresolved = widget.unresolved(&below_panel("BOOTING_text[2]")).unwrap();
+        assert_eq!(unresolved.namesakes, 1);
+        assert_eq!(
+            unresolved.suggestions,
+            vec!["BOOTING_text", "BOOTING_dot1[2]"]
+        );
+    }
+
+    #[test]
+    fn misspelled() {
+        let widget = fixture();
+        let unresolved = widget.unresolved(&below_panel("booting_dot[2]")).unwrap();
+        assert_eq!(unresolved.namesakes, 0);
+        assert_eq!(unresolved.suggestions[0], "BOOTING_dot1[2]");
+        assert_eq!(
+            widget
+                .fuzzy_path_names(&below_panel("booting_dot[2]"))
+                .unwrap(),
+            below_panel("BOOTING_dot1[2]")
+        );
+        // every occurrence is as close
+        let unresolved = widget.unresolved(&below_panel("BOOTING_dot")).unwrap();
+        assert_eq!(unresolved.suggestions.len(), 5);
+        assert!(
+            widget
+                .fuzzy_path_names(&below_panel("BOOTING_dot"))
+                .is_err()
+        );
+        // several misspelled names
+        assert_eq!(
+            widget
+                .fuzzy_path_names(&["main_canvas", "Booting_Canva", "Booting_H_Panel"])
+                .unwrap(),
+            PANEL.to_vec()
+        );
+    }
+
+    #[test]
+    fn nothing_close() {
+        let widget = fixture();
+        let unresolved = widget
+            .unresolved(&["MAIN_CANVAS", "nothing_alike"])
+            .unwrap();
+        assert_eq!(unresolved.resolved, vec![0]);
+        assert!(unresolved.suggestions.is_empty());
+        assert!(!unresolved.available.is_empty());
+        assert!(
+            widget
+                .fuzzy_path_names(&["MAIN_CANVAS", "nothing_alike"])
+                .is_err()
+        );
+    }
+}
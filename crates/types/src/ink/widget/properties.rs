@@ -3,7 +3,7 @@ use serde::{
     ser::{SerializeSeq, SerializeStruct},
 };
 
-use crate::{HandleId, Name};
+use crate::{Fidelity, HandleId, Name};
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Default, Clone, Deserialize, PartialEq)]
@@ -18,7 +18,12 @@ impl Serialize for inkPropertyBinding {
     where
         S: serde::Serializer,
     {
-        let mut s = serializer.serialize_struct("inkPropertyBinding", 2)?;
+        let trimmed = Fidelity::trimmed();
+        let mut s =
+            serializer.serialize_struct("inkPropertyBinding", if trimmed { 2 } else { 3 })?;
+        if !trimmed {
+            s.serialize_field("$type", "inkPropertyBinding")?;
+        }
         s.serialize_field("propertyName", &self.property_name)?;
         s.serialize_field("stylePath", &self.style_path)?;
         s.end()
@@ -37,6 +42,12 @@ impl Serialize for inkPropertyManager {
     where
         S: serde::Serializer,
     {
+        if !Fidelity::trimmed() {
+            let mut s = serializer.serialize_struct("inkPropertyManager", 2)?;
+            s.serialize_field("$type", "inkPropertyManager")?;
+            s.serialize_field("bindings", &self.bindings)?;
+            return s.end();
+        }
         let mut seq = serializer.serialize_seq(Some(self.bindings.len()))?;
         for elem in self.bindings.iter() {
             seq.serialize_element(elem)?;
@@ -57,6 +68,12 @@ impl Serialize for PropertyManager {
     where
         S: serde::Serializer,
     {
-        self.data.serialize(serializer)
+        if Fidelity::trimmed() {
+            return self.data.serialize(serializer);
+        }
+        let mut s = serializer.serialize_struct("PropertyManager", 2)?;
+        s.serialize_field("HandleId", &self.handle_id)?;
+        s.serialize_field("Data", &self.data)?;
+        s.end()
     }
 }
@@ -0,0 +1,193 @@
+//! Full-text search across widget content.
+//!
+//! Useful when only a visible string, a texture part or a style path is known.
+
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use super::{Walk, Widget, WidgetNode, inkWidgetLibraryResource};
+
+/// widget field holding searchable content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchableField {
+    Name,
+    Text,
+    LocalizationKey,
+    TexturePart,
+    TextureAtlas,
+    FontFamily,
+    StylePath,
+    PropertyName,
+}
+
+impl Display for SearchableField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Name => "name",
+                Self::Text => "text",
+                Self::LocalizationKey => "localization key",
+                Self::TexturePart => "texture part",
+                Self::TextureAtlas => "texture atlas",
+                Self::FontFamily => "font family",
+                Self::StylePath => "style path",
+                Self::PropertyName => "property name",
+            }
+        )
+    }
+}
+
+#[cfg(feature = "clap")]
+impl clap::ValueEnum for SearchableField {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self::Name,
+            Self::Text,
+            Self::LocalizationKey,
+            Self::TexturePart,
+            Self::TextureAtlas,
+            Self::FontFamily,
+            Self::StylePath,
+            Self::PropertyName,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(clap::builder::PossibleValue::new(match self {
+            Self::Name => "name",
+            Self::Text => "text",
+            Self::LocalizationKey => "localization-key",
+            Self::TexturePart => "texture-part",
+            Self::TextureAtlas => "texture-atlas",
+            Self::FontFamily => "font-family",
+            Self::StylePath => "style-path",
+            Self::PropertyName => "property-name",
+        }))
+    }
+}
+
+/// widget field whose content matched
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    pub node: WidgetNode<'a>,
+    pub field: SearchableField,
+    pub value: String,
+}
+
+impl Widget {
+    /// every non-empty searchable content, in declaration order
+    pub fn searchable(&self) -> Vec<(SearchableField, String)> {
+        let mut fields = vec![];
+        if let Some(name) = self.name() {
+            fields.push((SearchableField::Name, name.to_string()));
+        }
+        match self {
+            Self::inkTextWidget(text) => {
+                fields.push((SearchableField::Text, text.text.clone()));
+                if let Some(key) = text.localization_string.key() {
+                    fields.push((SearchableField::LocalizationKey, key.to_string()));
+                }
+                fields.push((
+                    SearchableField::FontFamily,
+                    text.font_family
+                        .depot_path()
+                        .as_path()
+                        .display()
+                        .to_string(),
+                ));
+            }
+            Self::inkImageWidget(image) => {
+                fields.push((
+                    SearchableField::TexturePart,
+                    image.texture_part.as_str().to_string(),
+                ));
+                fields.push((
+                    SearchableField::TextureAtlas,
+                    image
+                        .texture_atlas
+                        .depot_path()
+                        .as_path()
+                        .display()
+                        .to_string(),
+                ));
+            }
+            _ => {}
+        }
+        if let Some(manager) = self.as_widget().and_then(|x| x.property_manager()) {
+            for binding in manager.data.bindings.iter() {
+                fields.push((
+                    SearchableField::PropertyName,
+                    binding.property_name.as_str().to_string(),
+                ));
+                fields.push((
+                    SearchableField::StylePath,
+                    binding.style_path.as_str().to_string(),
+                ));
+            }
+        }
+        fields.retain(|(_, x)| !x.is_empty());
+        fields
+    }
+}
+
+impl inkWidgetLibraryResource {
+    /// every widget field whose content satisfies `matches`, in document order
+    pub fn search(&self, matches: impl Fn(&str) -> bool) -> Vec<SearchHit<'_>> {
+        self.walk()
+            .into_iter()
+            .flat_map(|node| {
+                node.widget
+                    .data
+                    .searchable()
+                    .into_iter()
+                    .filter(|(_, value)| matches(value))
+                    .map(move |(field, value)| SearchHit {
+                        node: node.clone(),
+                        field,
+                        value,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fidelity, File};
+
+    #[test]
+    fn compound_widgets_style_bindings() {
+        let widget = serde_json::from_str::<File<inkWidgetLibraryResource>>(include_str!(
+            "../../../../../inkwidget_biomoncheck.json"
+        ))
+        .unwrap()
+        .resource();
+        let hits = widget.search(|x| x == "MainColors.Red");
+        assert!(hits.iter().all(|x| x.field == SearchableField::StylePath));
+        for (indexes, name) in [
+            (vec![0, 0, 4], "Booting_H_Panel"),
+            (vec![0, 1], "Diagnostic_Canvas"),
+        ] {
+            let hit = hits
+                .iter()
+                .find(|x| x.node.indexes == indexes)
+                .unwrap_or_else(|| panic!("{name} binds MainColors.Red"));
+            assert_eq!(hit.node.names.last().map(|x| x.as_str()), Some(name));
+            assert!(hit.node.widget.data.as_compound().is_some());
+            // still written back as-is
+            let value = Fidelity::WolvenKit
+                .scope(|| serde_json::to_value(hit.node.widget))
+                .unwrap();
+            assert_eq!(
+                value["Data"]["propertyManager"]["Data"]["bindings"][0]["stylePath"]["$value"],
+                "MainColors.Red"
+            );
+        }
+    }
+}
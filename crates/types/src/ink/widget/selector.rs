@@ -0,0 +1,576 @@
+//! CSS-like selectors over the widget tree.
+//!
+//! e.g. `inkCanvasWidget > inkTextWidget[name^=warning][visible=false]`
+//!
+//! - class: `inkTextWidget`, or `*` for any widget
+//! - name: `#warning1`, shorthand for `[name=warning1]`
+//! - property: `[visible=false]`, `[layout.anchor=Centered]`, `[opacity<0.5]`,
+//!   or `[logicController]` for any non-null value
+//!   - operators: `=`, `!=`, `^=` (starts with), `$=` (ends with), `*=` (contains),
+//!     `<`, `<=`, `>`, `>=`
+//!   - values can be quoted, e.g. `[name="DIAGNOSTIC line"]`
+//! - combinators: descendant (space) and child (`>`)
+//! - alternatives: `inkTextWidget, inkImageWidget`
+//!
+//! Pseudo-classes (e.g. `:not(...)`) and sibling combinators (`+`, `~`) are not supported,
+//! and rejected as such: quote names containing these characters, e.g. `[name="a:b"]`.
+//!
+//! Properties are looked up by their dotted path in the WolvenKit export, so
+//! fields omitted from trimmed exports still match their defaults.
+
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use serde_json::Value;
+
+use super::{Classname, Walk, WidgetNode, inkWidgetLibraryResource};
+use crate::ink::Fidelity;
+
+/// parsed selector, see module documentation for its syntax
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    source: String,
+    alternatives: Vec<Complex>,
+}
+
+/// selector which could not be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectorError {
+    /// byte offset in the selector
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at {})", self.message, self.position)
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+/// compounds joined by combinators, e.g. `a > b c`
+#[derive(Debug, Clone, PartialEq)]
+struct Complex {
+    first: Compound,
+    rest: Vec<(Combinator, Compound)>,
+}
+
+/// a single widget requirements, e.g. `inkTextWidget#warning1[visible=true]`
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Compound {
+    class: Option<String>,
+    name: Option<String>,
+    predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    StartsWith,
+    EndsWith,
+    Contains,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Predicate {
+    property: String,
+    /// presence only, when unspecified
+    condition: Option<(Operator, String)>,
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+/// characters which end a class or a name
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '>' | ',' | '[' | ']' | '#' | '"' | '\'') || is_unsupported(c)
+}
+
+/// characters of CSS syntax which is not supported
+fn is_unsupported(c: char) -> bool {
+    matches!(c, ':' | '(' | ')' | '+' | '~')
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, SelectorError> {
+        Err(SelectorError {
+            position: self.position,
+            message: message.into(),
+        })
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        if self.rest().starts_with(expected) {
+            self.position += expected.len();
+            return true;
+        }
+        false
+    }
+
+    /// skip whitespaces, returning whether any was found
+    fn whitespaces(&mut self) -> bool {
+        let trimmed = self.rest().trim_start();
+        let skipped = self.rest().len() - trimmed.len();
+        self.position += skipped;
+        skipped > 0
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let end = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.position += end;
+        &rest[..end]
+    }
+
+    fn identifier(&mut self, what: &str) -> Result<String, SelectorError> {
+        let identifier = self.take_while(|c| !is_delimiter(c));
+        if identifier.is_empty() {
+            return self.error(format!("expected {what}"));
+        }
+        Ok(identifier.to_string())
+    }
+
+    fn value(&mut self) -> Result<String, SelectorError> {
+        for quote in ['"', '\''] {
+            if self.peek() == Some(quote) {
+                self.position += 1;
+                let value = self.take_while(|c| c != quote);
+                if !self.eat(&quote.to_string()) {
+                    return self.error("unterminated quoted value");
+                }
+                return Ok(value.to_string());
+            }
+        }
+        let value = self.take_while(|c| c != ']');
+        Ok(value.trim_end().to_string())
+    }
+
+    fn operator(&mut self) -> Option<Operator> {
+        // longest first
+        for (token, operator) in [
+            ("!=", Operator::NotEqual),
+            ("^=", Operator::StartsWith),
+            ("$=", Operator::EndsWith),
+            ("*=", Operator::Contains),
+            ("<=", Operator::LessOrEqual),
+            (">=", Operator::GreaterOrEqual),
+            ("=", Operator::Equal),
+            ("<", Operator::Less),
+            (">", Operator::Greater),
+        ] {
+            if self.eat(token) {
+                return Some(operator);
+            }
+        }
+        None
+    }
+
+    fn predicate(&mut self) -> Result<Predicate, SelectorError> {
+        self.whitespaces();
+        let start = self.position;
+        // only allowed as a prefix, e.g. `$type`, since `$=` is an operator
+        self.eat("$");
+        self.take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '.'));
+        let property = self.source[start..self.position].to_string();
+        if property.is_empty() || property == "$" {
+            self.position = start;
+            return self.error("expected property name");
+        }
+        self.whitespaces();
+        let condition = match self.operator() {
+            Some(operator) => {
+                self.whitespaces();
+                let value = self.value()?;
+                self.whitespaces();
+                Some((operator, value))
+            }
+            None => None,
+        };
+        if !self.eat("]") {
+            return self.error("expected ']'");
+        }
+        Ok(Predicate {
+            property,
+            condition,
+        })
+    }
+
+    fn compound(&mut self) -> Result<Compound, SelectorError> {
+        let mut compound = Compound::default();
+        if !self.eat("*") && self.peek().is_some_and(|c| !is_delimiter(c)) {
+            compound.class = Some(self.identifier("widget class")?);
+        }
+        loop {
+            if self.eat("#") {
+                compound.name = Some(self.identifier("widget name")?);
+            } else if self.eat("[") {
+                let predicate = self.predicate()?;
+                compound.predicates.push(predicate);
+            } else if let Some(c) = self.peek().filter(|c| is_unsupported(*c)) {
+                return self.error(format!("unsupported selector syntax '{c}'"));
+            } else {
+                break;
+            }
+        }
+        Ok(compound)
+    }
+
+    fn complex(&mut self) -> Result<Complex, SelectorError> {
+        self.whitespaces();
+        let start = self.position;
+        let first = self.compound()?;
+        if self.position == start {
+            return self.error("expected widget class, name or property");
+        }
+        let mut rest = vec![];
+        loop {
+            let spaced = self.whitespaces();
+            let combinator = if self.eat(">") {
+                self.whitespaces();
+                Combinator::Child
+            } else if spaced && self.peek().is_some_and(|c| c != ',') {
+                Combinator::Descendant
+            } else {
+                break;
+            };
+            let start = self.position;
+            let compound = self.compound()?;
+            if self.position == start {
+                return self.error("expected widget class, name or property after combinator");
+            }
+            rest.push((combinator, compound));
+        }
+        Ok(Complex { first, rest })
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            source: s,
+            position: 0,
+        };
+        let mut alternatives = vec![parser.complex()?];
+        while parser.eat(",") {
+            alternatives.push(parser.complex()?);
+        }
+        if parser.position < s.len() {
+            return parser.error(format!(
+                "unexpected '{}'",
+                parser.peek().unwrap_or_default()
+            ));
+        }
+        Ok(Self {
+            source: s.to_string(),
+            alternatives,
+        })
+    }
+}
+
+impl Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// look up a property by its dotted path, e.g. `layout.margin.left`,
+/// unwrapping handles and `$value` along the way
+fn lookup<'a>(mut value: &'a Value, property: &str) -> Option<&'a Value> {
+    fn unwrap(value: &Value) -> &Value {
+        match value {
+            Value::Object(map) => match (map.get("$value"), map.get("Data")) {
+                (Some(inner), _) => inner,
+                (None, Some(inner)) if map.contains_key("HandleId") => unwrap(inner),
+                _ => value,
+            },
+            _ => value,
+        }
+    }
+    for segment in property.split('.') {
+        value = match unwrap(value) {
+            Value::Object(map) => map.get(segment).or_else(|| {
+                map.iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(segment))
+                    .map(|(_, x)| x)
+            })?,
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(unwrap(value))
+}
+
+/// numeric reading of a property, where booleans are exported as 0 or 1
+fn numeric(value: &str) -> Option<f32> {
+    match value {
+        "true" => Some(1.),
+        "false" => Some(0.),
+        _ => value.parse::<f32>().ok(),
+    }
+}
+
+impl Predicate {
+    fn matches(&self, widget: &Value) -> bool {
+        let Some(found) = lookup(widget, &self.property).filter(|x| !x.is_null()) else {
+            return false;
+        };
+        let Some((operator, expected)) = &self.condition else {
+            return true;
+        };
+        let text = match found {
+            Value::String(x) => x.clone(),
+            Value::Bool(x) => x.to_string(),
+            Value::Number(x) => x.to_string(),
+            // e.g. arrays or structs
+            _ => found.to_string(),
+        };
+        let number = match found {
+            Value::Bool(x) => Some(if *x { 1. } else { 0. }),
+            Value::Number(x) => x.as_f64().map(|x| x as f32),
+            _ => None,
+        };
+        let ordering = number
+            .zip(numeric(expected))
+            .and_then(|(found, expected)| found.partial_cmp(&expected));
+        match operator {
+            Operator::Equal => ordering.map(|x| x.is_eq()).unwrap_or(text == *expected),
+            Operator::NotEqual => ordering.map(|x| x.is_ne()).unwrap_or(text != *expected),
+            Operator::StartsWith => text.starts_with(expected.as_str()),
+            Operator::EndsWith => text.ends_with(expected.as_str()),
+            Operator::Contains => text.contains(expected.as_str()),
+            Operator::Less => ordering.is_some_and(|x| x.is_lt()),
+            Operator::LessOrEqual => ordering.is_some_and(|x| x.is_le()),
+            Operator::Greater => ordering.is_some_and(|x| x.is_gt()),
+            Operator::GreaterOrEqual => ordering.is_some_and(|x| x.is_ge()),
+        }
+    }
+}
+
+impl Compound {
+    fn matches(&self, node: &WidgetNode) -> bool {
+        if let Some(class) = &self.class
+            && node.widget.data.classname() != *class
+        {
+            return false;
+        }
+        if let Some(name) = &self.name
+            && node.names.last() != Some(name)
+        {
+            return false;
+        }
+        if self.predicates.is_empty() {
+            return true;
+        }
+        let widget = Fidelity::WolvenKit
+            .scope(|| serde_json::to_value(&node.widget.data))
+            .expect("widget should serialize");
+        self.predicates.iter().all(|x| x.matches(&widget))
+    }
+}
+
+impl Complex {
+    /// whether `node` matches up to the compound at `depth`, from the right
+    fn matches(
+        &self,
+        depth: usize,
+        node: &WidgetNode,
+        nodes: &HashMap<&[usize], &WidgetNode>,
+    ) -> bool {
+        let (compound, combinator) = match depth {
+            0 => (&self.first, None),
+            _ => {
+                let (combinator, compound) = &self.rest[depth - 1];
+                (compound, Some(*combinator))
+            }
+        };
+        if !compound.matches(node) {
+            return false;
+        }
+        let Some(combinator) = combinator else {
+            return true;
+        };
+        let mut ancestors = (0..node.indexes.len())
+            .rev()
+            .filter_map(|len| nodes.get(&node.indexes[..len]));
+        match combinator {
+            Combinator::Child => ancestors
+                .next()
+                .is_some_and(|parent| self.matches(depth - 1, parent, nodes)),
+            Combinator::Descendant => {
+                ancestors.any(|ancestor| self.matches(depth - 1, ancestor, nodes))
+            }
+        }
+    }
+}
+
+impl Selector {
+    /// whether the widget matches this selector, given every other widget
+    /// of the tree to resolve its ancestors
+    fn matches(&self, node: &WidgetNode, nodes: &HashMap<&[usize], &WidgetNode>) -> bool {
+        self.alternatives
+            .iter()
+            .any(|x| x.matches(x.rest.len(), node, nodes))
+    }
+}
+
+impl inkWidgetLibraryResource {
+    /// every widget matching the selector, in document order
+    ///
+    /// note: the root widget itself is never selected, just like it is
+    /// never part of path indexes
+    pub fn select(&self, selector: &Selector) -> Vec<WidgetNode<'_>> {
+        let walked = self.walk();
+        let nodes: HashMap<&[usize], &WidgetNode> =
+            walked.iter().map(|x| (x.indexes.as_slice(), x)).collect();
+        walked
+            .iter()
+            .filter(|x| selector.matches(x, &nodes))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::File;
+
+    fn predicate(selector: &str) -> Predicate {
+        let selector = selector.parse::<Selector>().unwrap();
+        selector.alternatives[0].first.predicates[0].clone()
+    }
+
+    fn names(selector: &str) -> Vec<String> {
+        let widget = serde_json::from_str::<File<inkWidgetLibraryResource>>(include_str!(
+            "../../../../../inkwidget_biomoncheck.json"
+        ))
+        .unwrap()
+        .resource();
+        widget
+            .select(&selector.parse().unwrap())
+            .into_iter()
+            .map(|x| x.names.join("."))
+            .collect()
+    }
+
+    #[test]
+    fn operators() {
+        for (token, operator) in [
+            ("=", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            ("^=", Operator::StartsWith),
+            ("$=", Operator::EndsWith),
+            ("*=", Operator::Contains),
+            ("<", Operator::Less),
+            ("<=", Operator::LessOrEqual),
+            (">", Operator::Greater),
+            (">=", Operator::GreaterOrEqual),
+        ] {
+            assert_eq!(
+                predicate(&format!("[name{token}text]")),
+                Predicate {
+                    property: "name".to_string(),
+                    condition: Some((operator, "text".to_string())),
+                },
+                "{token}"
+            );
+        }
+    }
+
+    #[test]
+    fn properties() {
+        assert_eq!(predicate("[$type]").property, "$type");
+        assert_eq!(
+            predicate("[$type$=Widget]"),
+            Predicate {
+                property: "$type".to_string(),
+                condition: Some((Operator::EndsWith, "Widget".to_string())),
+            }
+        );
+        assert_eq!(
+            predicate("[ layout.anchor = 'Centered' ]"),
+            Predicate {
+                property: "layout.anchor".to_string(),
+                condition: Some((Operator::Equal, "Centered".to_string())),
+            }
+        );
+        assert_eq!(predicate("[logicController]").condition, None);
+    }
+
+    #[test]
+    fn combinators() {
+        let selector = "inkCanvasWidget#a > * [visible], inkTextWidget"
+            .parse::<Selector>()
+            .unwrap();
+        assert_eq!(selector.alternatives.len(), 2);
+        let first = &selector.alternatives[0];
+        assert_eq!(first.first.class.as_deref(), Some("inkCanvasWidget"));
+        assert_eq!(first.first.name.as_deref(), Some("a"));
+        assert_eq!(
+            first.rest.iter().map(|(x, _)| *x).collect::<Vec<_>>(),
+            vec![Combinator::Child, Combinator::Descendant]
+        );
+        assert_eq!(first.rest[0].1, Compound::default());
+        assert_eq!(first.rest[1].1.predicates.len(), 1);
+    }
+
+    #[test]
+    fn invalid() {
+        for selector in [
+            "",
+            "[name",
+            "[=text]",
+            "[$=text]",
+            "[name='text]",
+            "inkTextWidget >",
+            "inkTextWidget:not(inkImageWidget)",
+            ":first-child",
+            "a + b",
+            "a ~ b",
+        ] {
+            assert!(selector.parse::<Selector>().is_err(), "{selector}");
+        }
+    }
+
+    #[test]
+    fn matching() {
+        assert_eq!(
+            names("[name$=percent_text]"),
+            vec!["MAIN_CANVAS.Booting_Canvas.Booting_percent_text"]
+        );
+        assert_eq!(
+            names("#MAIN_CANVAS > inkCanvasWidget"),
+            names("#MAIN_CANVAS > [name$=_Canvas]")
+        );
+        assert!(
+            names("#Diagnostic_Canvas inkTextWidget")
+                .iter()
+                .all(|x| x.starts_with("MAIN_CANVAS.Diagnostic_Canvas."))
+        );
+        assert!(names("#Booting_Canvas > #DIAGNOSTIC_line1").is_empty());
+        assert_eq!(names("#Diagnostic_Canvas_1 > #DIAGNOSTIC_line1").len(), 1);
+    }
+}
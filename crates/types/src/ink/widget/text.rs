@@ -0,0 +1,291 @@
+//! Approximate text layout, to detect texts overflowing their box.
+//!
+//! Glyph advances come from the font itself (see [FontMetrics]),
+//! but shaping, ligatures and font fallback are ignored.
+
+use super::{
+    font::{textLetterCase, textOverflowPolicy, textWrappingPolicy},
+    inkTextWidget,
+};
+
+/// appended by the game when truncating text
+const DOTS: &str = "...";
+/// font sizes tried before giving up on shrinking text
+const SHRINK_STEPS: usize = 20;
+/// tolerance for rounding errors, in pixels
+const EPSILON: f32 = 0.01;
+
+/// horizontal metrics of a font, in em (`1.` = font size)
+pub trait FontMetrics {
+    /// horizontal advance of a character
+    fn advance(&self, c: char) -> f32;
+    /// adjustment between two consecutive characters
+    fn kerning(&self, _left: char, _right: char) -> f32 {
+        0.
+    }
+    /// distance between two consecutive baselines
+    fn line_height(&self) -> f32;
+}
+
+/// text broken into lines
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    pub lines: Vec<String>,
+    /// widest line, in pixels
+    pub width: f32,
+    /// all lines stacked, in pixels
+    pub height: f32,
+}
+
+/// how the text renders inside its box
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextFit {
+    /// text fits entirely
+    Fits,
+    /// text spills out of its box, by this many pixels
+    Overflows { width: f32, height: f32 },
+    /// text gets cut, ending with dots
+    Truncated { visible: Vec<String> },
+    /// text scrolls back and forth, over this many pixels
+    Scrolls { distance: f32 },
+    /// font shrinks down to this size
+    Shrinks { font_size: f32 },
+}
+
+impl TextFit {
+    /// whether part of the text cannot be seen at all
+    pub fn overflows(&self) -> bool {
+        matches!(self, Self::Overflows { .. } | Self::Truncated { .. })
+    }
+}
+
+impl std::fmt::Display for TextFit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fits => write!(f, "✅ fits"),
+            Self::Overflows { width, height } => {
+                write!(f, "❌ overflows (width: +{width}, height: +{height})")
+            }
+            Self::Truncated { visible } => write!(f, "✂️  truncated: {}", visible.join(" ⏎ ")),
+            Self::Scrolls { distance } => write!(f, "↔️  scrolls over {distance}"),
+            Self::Shrinks { font_size } => write!(f, "🔍 shrinks to font size {font_size}"),
+        }
+    }
+}
+
+struct Measure<'a, M: FontMetrics> {
+    metrics: &'a M,
+    font_size: f32,
+    /// extra spacing between characters, in pixels
+    tracking: f32,
+}
+
+impl<M: FontMetrics> Measure<'_, M> {
+    fn width(&self, text: &str) -> f32 {
+        let mut width = 0.;
+        let mut previous: Option<char> = None;
+        for (idx, c) in text.chars().enumerate() {
+            width += self.metrics.advance(c) * self.font_size;
+            if let Some(previous) = previous {
+                width += self.metrics.kerning(previous, c) * self.font_size;
+            }
+            if idx > 0 {
+                width += self.tracking;
+            }
+            previous = Some(c);
+        }
+        width
+    }
+}
+
+/// break a single paragraph, greedily
+fn wrap<M: FontMetrics>(
+    paragraph: &str,
+    measure: &Measure<'_, M>,
+    width: f32,
+    per_character: bool,
+) -> Vec<String> {
+    let mut lines = vec![];
+    let mut current = String::new();
+    let tokens: Vec<String> = if per_character {
+        paragraph.chars().map(String::from).collect()
+    } else {
+        paragraph
+            .split_inclusive(' ')
+            .map(|x| x.to_string())
+            .collect()
+    };
+    for token in tokens {
+        let candidate = format!("{current}{token}");
+        if measure.width(candidate.trim_end()) <= width + EPSILON || current.is_empty() {
+            current = candidate;
+        } else {
+            lines.push(current.trim_end().to_string());
+            current = token;
+        }
+        // a single word wider than the box gets broken anywhere
+        while measure.width(current.trim_end()) > width + EPSILON && current.chars().count() > 1 {
+            let mut head = String::new();
+            for c in current.chars() {
+                if !head.is_empty() && measure.width(&format!("{head}{c}")) > width + EPSILON {
+                    break;
+                }
+                head.push(c);
+            }
+            current = current[head.len()..].to_string();
+            lines.push(head);
+        }
+    }
+    lines.push(current.trim_end().to_string());
+    lines
+}
+
+/// longest prefix which fits `width` once followed by dots
+fn truncate<M: FontMetrics>(line: &str, measure: &Measure<'_, M>, width: f32) -> String {
+    let mut out = String::new();
+    for c in line.chars() {
+        if measure.width(&format!("{out}{c}{DOTS}")) > width + EPSILON {
+            break;
+        }
+        out.push(c);
+    }
+    format!("{}{DOTS}", out.trim_end())
+}
+
+impl inkTextWidget {
+    /// text as displayed, once letter case is applied
+    pub fn displayed(&self, text: &str) -> String {
+        match self.text_letter_case {
+            Some(textLetterCase::UpperCase) => text.to_uppercase(),
+            Some(textLetterCase::LowerCase) => text.to_lowercase(),
+            _ => text.to_string(),
+        }
+    }
+
+    fn measure<'a, M: FontMetrics>(&self, metrics: &'a M, font_size: f32) -> Measure<'a, M> {
+        Measure {
+            metrics,
+            font_size,
+            // tracking is expressed in thousandths of an em
+            tracking: self.tracking * font_size / 1000.,
+        }
+    }
+
+    fn line_height<M: FontMetrics>(&self, metrics: &M, font_size: f32) -> f32 {
+        let percentage = if self.line_height_percentage > 0. {
+            self.line_height_percentage
+        } else {
+            1.
+        };
+        metrics.line_height() * font_size * percentage
+    }
+
+    fn layout_with<M: FontMetrics>(
+        &self,
+        text: &str,
+        metrics: &M,
+        font_size: f32,
+        width: f32,
+    ) -> TextLayout {
+        let measure = self.measure(metrics, font_size);
+        let wrapping = &self.wrapping_info;
+        let limit = if !wrapping.auto_wrapping_enabled {
+            f32::INFINITY
+        } else if wrapping.wrapping_at_position > 0. {
+            wrapping.wrapping_at_position
+        } else {
+            width
+        };
+        let per_character = wrapping.wrapping_policy == textWrappingPolicy::PerCharacter;
+        let lines: Vec<String> = self
+            .displayed(text)
+            .split('\n')
+            .flat_map(|paragraph| wrap(paragraph, &measure, limit, per_character))
+            .collect();
+        TextLayout {
+            width: lines.iter().map(|x| measure.width(x)).fold(0., f32::max),
+            height: lines.len() as f32 * self.line_height(metrics, font_size),
+            lines,
+        }
+    }
+
+    /// break text into lines, as it would be laid out in a box `width` pixels wide
+    pub fn layout_text<M: FontMetrics>(&self, text: &str, metrics: &M, width: f32) -> TextLayout {
+        self.layout_with(text, metrics, self.font_size, width)
+    }
+
+    /// how text renders in a box of the given size, under `policy`
+    pub fn fit<M: FontMetrics>(
+        &self,
+        text: &str,
+        metrics: &M,
+        (width, height): (f32, f32),
+        policy: &textOverflowPolicy,
+    ) -> TextFit {
+        let measure = self.measure(metrics, self.font_size);
+        let line_height = self.line_height(metrics, self.font_size);
+        // dots and scrolling both keep text on a single line
+        let single = self.displayed(text).replace('\n', " ");
+        let single_width = measure.width(&single);
+        let layout = self.layout_text(text, metrics, width);
+        let too_wide = layout.width > width + EPSILON;
+        let too_high = layout.height > height + EPSILON;
+        match policy {
+            _ if !too_wide && !too_high => TextFit::Fits,
+            textOverflowPolicy::None => TextFit::Overflows {
+                width: (layout.width - width).max(0.),
+                height: (layout.height - height).max(0.),
+            },
+            textOverflowPolicy::DotsEnd
+            | textOverflowPolicy::AutoScroll
+            | textOverflowPolicy::PingPongScroll
+                if line_height > height + EPSILON =>
+            {
+                TextFit::Overflows {
+                    width: (single_width - width).max(0.),
+                    height: line_height - height,
+                }
+            }
+            textOverflowPolicy::DotsEnd if single_width > width + EPSILON => TextFit::Truncated {
+                visible: vec![truncate(&single, &measure, width)],
+            },
+            textOverflowPolicy::AutoScroll | textOverflowPolicy::PingPongScroll
+                if single_width > width + EPSILON =>
+            {
+                TextFit::Scrolls {
+                    distance: single_width - width,
+                }
+            }
+            textOverflowPolicy::DotsEnd
+            | textOverflowPolicy::AutoScroll
+            | textOverflowPolicy::PingPongScroll => TextFit::Fits,
+            textOverflowPolicy::DotsEndLastLine => {
+                let count = ((height + EPSILON) / line_height).floor().max(1.) as usize;
+                let hidden = layout.lines.len() > count;
+                let mut visible: Vec<String> = layout.lines.into_iter().take(count).collect();
+                let last = visible.len() - 1;
+                for (idx, line) in visible.iter_mut().enumerate() {
+                    if (idx == last && hidden) || measure.width(line) > width + EPSILON {
+                        *line = truncate(line, &measure, width);
+                    }
+                }
+                TextFit::Truncated { visible }
+            }
+            textOverflowPolicy::AdjustToSize => {
+                let (mut low, mut high) = (0., self.font_size);
+                for _ in 0..SHRINK_STEPS {
+                    let middle = (low + high) / 2.;
+                    let layout = self.layout_with(text, metrics, middle, width);
+                    if layout.width <= width + EPSILON && layout.height <= height + EPSILON {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+                TextFit::Shrinks {
+                    font_size: (low * 10.).floor() / 10.,
+                }
+            }
+        }
+    }
+}